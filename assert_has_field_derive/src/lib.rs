@@ -0,0 +1,222 @@
+//! Derive macro companion for the [`assert_has_field`](https://docs.rs/assert_has_field) crate.
+//!
+//! The derive macro is re-exported by `assert_has_field` when its `derive` feature is enabled
+//! and is not meant to be used directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Group, TokenStream as TokenStream2, TokenTree};
+use quote::{ToTokens, quote};
+use syn::punctuated::Punctuated;
+use syn::{
+    Attribute, Data, DataEnum, DeriveInput, Field, Fields, Index, Member, Meta, Token, Visibility,
+    parse_macro_input,
+};
+
+/// Implements `assert_has_field::HasField` for every field of the struct or union that is `pub`
+/// or belongs to a struct that isn't `pub` and, unless the fields can't be safely referenced,
+/// `assert_has_field::GetField` for the `pub` fields and the fields marked with `#[has_field(get)]`.
+///
+/// For an enum, lists its variants and the fields of every variant so that
+/// `assert_has_field!(enum Enum::*, field)` can check all of them.
+#[proc_macro_derive(HasField, attributes(has_field))]
+pub fn derive_has_field(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    // The fields of unions and `#[repr(packed)]` structs can't be safely referenced,
+    // so they only get the field list without the accessors.
+    let (fields, accessible) = match &input.data {
+        Data::Struct(data) => (data.fields.clone(), !is_packed(input)?),
+        Data::Union(data) => (Fields::Named(data.fields.clone()), false),
+        Data::Enum(data) => return Ok(expand_enum(input, data)),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let is_pub = |vis: &Visibility| matches!(vis, Visibility::Public(_));
+
+    let impls = fields.iter().enumerate().map(|(index, field)| {
+        let field_name = field_name(index, field);
+        // The accessors would bypass the privacy of the field, so the private fields must opt in.
+        let get_attr = get_attr(field)?;
+        if let (Some(attr), false) = (get_attr, accessible) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "the fields of unions and `#[repr(packed)]` structs can't be safely referenced",
+            ));
+        }
+        let gettable = is_pub(&field.vis) || get_attr.is_some();
+        let field_ty = &field.ty;
+        let declaration = quote! {
+            impl #impl_generics ::assert_has_field::secret::DeclaresField<
+                { ::assert_has_field::field_id(#field_name) },
+                #field_ty,
+            > for #name #ty_generics #where_clause
+            {
+            }
+        };
+        // The implementations for a `pub` struct are public, so their associated types can't name
+        // the private types, which the private fields may be of.
+        if is_pub(&input.vis) && !gettable {
+            return Ok(declaration);
+        }
+
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(index)),
+        };
+        let accessors = (accessible && gettable).then(|| {
+            quote! {
+                impl #impl_generics ::assert_has_field::GetField<{ ::assert_has_field::field_id(#field_name) }>
+                    for #name #ty_generics #where_clause
+                {
+                    fn get(&self) -> &Self::Type {
+                        &self.#member
+                    }
+
+                    fn get_mut(&mut self) -> &mut Self::Type {
+                        &mut self.#member
+                    }
+                }
+            }
+        });
+        Ok(quote! {
+            #declaration
+
+            impl #impl_generics ::assert_has_field::HasField<{ ::assert_has_field::field_id(#field_name) }>
+                for #name #ty_generics #where_clause
+            {
+                type Type = #field_ty;
+            }
+
+            #accessors
+        })
+    });
+    let impls = impls.collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        impl #impl_generics ::assert_has_field::secret::DerivedHasField
+            for #name #ty_generics #where_clause
+        {
+        }
+
+        #(#impls)*
+    })
+}
+
+fn expand_enum(input: &DeriveInput, data: &DataEnum) -> TokenStream2 {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Every variant gets a marker type named after it, so that the errors name the offending variant.
+    // The markers live in a module so that they don't shadow the types of the fields.
+    let markers = data.variants.iter().map(|variant| &variant.ident);
+    let list = data
+        .variants
+        .iter()
+        .rev()
+        .fold(quote! { () }, |list, variant| {
+            let marker = &variant.ident;
+            quote! { (variants::#marker, #list) }
+        });
+
+    let (impl_generics, ty_generics) = (&impl_generics, &ty_generics);
+    let impls = data.variants.iter().flat_map(|variant| {
+        let marker = &variant.ident;
+        variant
+            .fields
+            .iter()
+            .enumerate()
+            .map(move |(index, field)| {
+                let field_name = field_name(index, field);
+                // Within the impl for the marker, `Self` would name the marker instead of the enum.
+                let enum_ty = quote! { #name #ty_generics };
+                let field_ty = replace_self(field.ty.to_token_stream(), &enum_ty);
+                quote! {
+                    impl #impl_generics ::assert_has_field::secret::VariantHasField<
+                        #name #ty_generics,
+                        { ::assert_has_field::field_id(#field_name) },
+                    > for variants::#marker #where_clause
+                    {
+                        type Type = #field_ty;
+                    }
+                }
+            })
+    });
+
+    quote! {
+        const _: () = {
+            #[allow(non_camel_case_types)]
+            pub mod variants {
+                #(pub struct #markers;)*
+            }
+
+            impl #impl_generics ::assert_has_field::secret::Variants for #name #ty_generics #where_clause {
+                type List = #list;
+            }
+
+            #(#impls)*
+        };
+    }
+}
+
+/// Replaces every `Self` in the tokens with the given type.
+fn replace_self(tokens: TokenStream2, ty: &TokenStream2) -> TokenStream2 {
+    tokens
+        .into_iter()
+        .flat_map(|token| match token {
+            TokenTree::Ident(ident) if ident == "Self" => ty.clone(),
+            TokenTree::Group(group) => {
+                let mut replaced = Group::new(group.delimiter(), replace_self(group.stream(), ty));
+                replaced.set_span(group.span());
+                TokenTree::Group(replaced).into_token_stream()
+            }
+            token => token.into_token_stream(),
+        })
+        .collect()
+}
+
+/// Checks whether any of the `#[repr(...)]` attributes has the `packed` hint.
+fn is_packed(input: &DeriveInput) -> syn::Result<bool> {
+    for attr in &input.attrs {
+        if !attr.path().is_ident("repr") {
+            continue;
+        }
+        let hints = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        if hints.iter().any(|hint| hint.path().is_ident("packed")) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Finds the `#[has_field(get)]` attribute of the field.
+fn get_attr(field: &Field) -> syn::Result<Option<&Attribute>> {
+    let mut found = None;
+    for attr in &field.attrs {
+        if !attr.path().is_ident("has_field") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("get") {
+                found = Some(attr);
+                Ok(())
+            } else {
+                Err(meta.error("expected `get`"))
+            }
+        })?;
+    }
+    Ok(found)
+}
+
+fn field_name(index: usize, field: &Field) -> String {
+    // Tuple fields are named by their index, just like in the field access expressions
+    match &field.ident {
+        Some(ident) => ident.to_string(),
+        None => index.to_string(),
+    }
+}
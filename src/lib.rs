@@ -1,6 +1,22 @@
 #![no_std]
 #![doc = include_str!("../README.md")]
 
+// Allows `#[derive(HasField)]` to refer to `::assert_has_field` from within this crate.
+extern crate self as assert_has_field;
+
+/// Derives [`HasField`](trait@HasField) for every field of a struct and [`GetField`] for its `pub` fields,
+/// or lists the fields of every variant of an enum for `assert_has_field!(enum Enum::*, ...)`.
+///
+/// A private field gets [`GetField`] when it is marked with `#[has_field(get)]`. Otherwise, for a `pub` struct,
+/// it doesn't get `HasField` either, because its type may be private.
+///
+/// Since the fields of unions and `#[repr(packed)]` structs can't be safely referenced,
+/// only `HasField` is derived for them.
+///
+/// Available with the `derive` feature.
+#[cfg(feature = "derive")]
+pub use assert_has_field_derive::HasField;
+
 #[doc(hidden)]
 pub mod secret {
     // Source: https://github.com/WorldSEnder/type-equalities-rs/tree/0a1aac50899ae966147ac6c917dbcc07da6a3626
@@ -17,24 +33,467 @@ pub mod secret {
     impl<T: ?Sized, U: ?Sized> IsEqual<U> for T where T: AliasSelf<Alias = U> {}
 
     // Source: https://stackoverflow.com/a/70978292/8341513
-    // The function cannot be const at the time of writing because
-    // destructor of `T` cannot be evaluated at compile-time and const drop is unstable
-    pub fn ty_must_eq<T, U>(_: T)
+    // The function takes a pointer instead of a value so that the field doesn't have to be moved
+    // out of the struct, which is impossible for the structs that implement `Drop`.
+    pub const fn ty_must_eq<T, U>(_: *const T)
+    where
+        T: ?Sized + IsEqual<U>,
+        U: ?Sized,
+    {
+    }
+
+    #[diagnostic::on_unimplemented(
+        message = "the asserted field of `{S}` is of type `{Self}`, not `{U}`",
+        label = "expected `{U}`, found `{Self}`"
+    )]
+    pub trait FieldOfType<S: ?Sized, U: ?Sized> {}
+    impl<S: ?Sized, T: ?Sized> FieldOfType<S, T> for T {}
+
+    // Implemented for the asserted type rather than for the field, so that the error points at
+    // the asserted type, which tells the field apart when the assertion comes from a shape.
+    // The impl doesn't constrain `T`, so the field type is still inferred from the field.
+    pub trait AssertedTypeOf<S: ?Sized, T: ?Sized> {}
+    impl<S: ?Sized, T: ?Sized, U: ?Sized> AssertedTypeOf<S, T> for U where T: FieldOfType<S, U> {}
+
+    // Unlike `ty_must_eq`, names the struct in the error, so that the failing struct
+    // can be told apart when one assertion checks several structs.
+    pub const fn field_must_be_of_type<S, T, U>(_: *const T)
+    where
+        S: ?Sized,
+        T: ?Sized,
+        U: ?Sized + AssertedTypeOf<S, T>,
+    {
+    }
+
+    // The traits below are implemented for the struct rather than for the field, so that the errors
+    // point at the struct and name it in the "required for" notes.
+    pub trait FieldConvertsInto<T, U> {}
+    impl<S: ?Sized, T: Into<U>, U> FieldConvertsInto<T, U> for S {}
+
+    pub const fn field_must_convert_into<S, T, U>(_: *const T)
+    where
+        S: ?Sized + FieldConvertsInto<T, U>,
+    {
+    }
+
+    pub trait FieldTryConvertsInto<T, U> {}
+    impl<S: ?Sized, T: TryInto<U>, U> FieldTryConvertsInto<T, U> for S {}
+
+    pub const fn field_must_try_convert_into<S, T, U>(_: *const T)
+    where
+        S: ?Sized + FieldTryConvertsInto<T, U>,
+    {
+    }
+
+    // `S: FieldNotOfType<T, U, _>` is ambiguous if and only if `T` is `U`, just like `NotEqual` below.
+    pub trait FieldNotOfType<T: ?Sized, U: ?Sized, A> {}
+    impl<S: ?Sized, T: ?Sized, U: ?Sized> FieldNotOfType<T, U, ()> for S {}
+    impl<S: ?Sized, T: ?Sized, U: ?Sized> FieldNotOfType<T, U, u8> for S where T: IsEqual<U> {}
+
+    pub const fn field_must_not_be_of_type<S, T, U, A>(_: *const T)
+    where
+        S: ?Sized + FieldNotOfType<T, U, A>,
+        T: ?Sized,
+        U: ?Sized,
+    {
+    }
+
+    // Source: https://github.com/nvzqz/static-assertions/blob/18bc65a094d890fe1faa5d3ccb70f12b89eabf56/src/assert_impl.rs#L288
+    // `T: NotEqual<U, _>` is ambiguous if and only if `T` is `U`.
+    pub trait NotEqual<U: ?Sized, A> {}
+    impl<T: ?Sized, U: ?Sized> NotEqual<U, ()> for T {}
+    impl<T: ?Sized, U: ?Sized> NotEqual<U, u8> for T where T: IsEqual<U> {}
+
+    pub const fn ty_must_not_eq<T, U, A>(_: *const T)
+    where
+        T: ?Sized + NotEqual<U, A>,
+        U: ?Sized,
+    {
+    }
+
+    // The lexer turns a nested tuple index such as `0.1` into a single float literal,
+    // which would be hashed as the name of a nonexistent field.
+    pub const fn segment_id(segment: &str) -> u64 {
+        let bytes = segment.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'.' {
+                panic!(
+                    "a nested tuple index such as `0.1` must be written with a space, e.g. `0 .1`"
+                );
+            }
+            i += 1;
+        }
+        crate::field_id(segment)
+    }
+
+    // `*mut T` is invariant in `T`, so `T` is exactly the type of the target
+    // and the source must be a subtype of it.
+    pub const fn must_be_subtype<T: ?Sized>(_target: *mut T, _source: *const T) {}
+
+    pub const fn ty_must_convert_into<T, U>(_: *const T)
+    where
+        T: Into<U>,
+    {
+    }
+
+    pub const fn ty_must_try_convert_into<T, U>(_: *const T)
+    where
+        T: TryInto<U>,
+    {
+    }
+
+    /// Produces a value of the pointee type without moving anything out of the pointee.
+    ///
+    /// It is meant to be called only in unreachable code.
+    pub const fn value_behind<T>(_: *const T) -> T {
+        core::unreachable!()
+    }
+
+    /// Produces a value of any type, e.g. the type inferred from a pattern.
+    ///
+    /// It is meant to be called only in unreachable code.
+    pub const fn unreachable_value<T>() -> T {
+        core::unreachable!()
+    }
+
+    /// Implemented by `#[derive(HasField)]` alongside the [`HasField`](crate::HasField) implementations
+    /// so that the absence of the latter can be trusted.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` doesn't derive `HasField`",
+        label = "the absence of a field can only be asserted for types with `#[derive(HasField)]`"
+    )]
+    pub trait DerivedHasField {}
+
+    pub const fn must_derive_has_field<T>()
+    where
+        T: ?Sized + DerivedHasField,
+    {
+    }
+
+    /// Implemented by `#[derive(HasField)]` for enums. The list is a nested tuple
+    /// `(Variant1, (Variant2, ()))` of the marker types of the variants.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` doesn't derive `HasField`",
+        label = "the fields of all variants can only be asserted for enums with `#[derive(HasField)]`"
+    )]
+    pub trait Variants {
+        type List;
+    }
+
+    /// Implemented by `#[derive(HasField)]` on the marker type of an enum variant for every field of the variant.
+    #[diagnostic::on_unimplemented(
+        message = "variant `{Self}` of `{E}` doesn't have the asserted field"
+    )]
+    pub trait VariantHasField<E: ?Sized, const NAME: u64> {
+        type Type: ?Sized;
+    }
+
+    pub trait AllVariantsHaveField<E: ?Sized, const NAME: u64> {}
+    impl<E: ?Sized, const NAME: u64> AllVariantsHaveField<E, NAME> for () {}
+    impl<E, V, Rest, const NAME: u64> AllVariantsHaveField<E, NAME> for (V, Rest)
+    where
+        E: ?Sized,
+        V: VariantHasField<E, NAME>,
+        Rest: AllVariantsHaveField<E, NAME>,
+    {
+    }
+
+    pub trait AllVariantsHaveFieldOfType<E: ?Sized, const NAME: u64, U: ?Sized> {}
+    impl<E: ?Sized, U: ?Sized, const NAME: u64> AllVariantsHaveFieldOfType<E, NAME, U> for () {}
+    impl<E, U, V, Rest, const NAME: u64> AllVariantsHaveFieldOfType<E, NAME, U> for (V, Rest)
+    where
+        E: ?Sized,
+        U: ?Sized,
+        V: VariantHasField<E, NAME, Type = U>,
+        Rest: AllVariantsHaveFieldOfType<E, NAME, U>,
+    {
+    }
+
+    pub const fn all_variants_have_field<E, const NAME: u64>()
+    where
+        E: ?Sized + Variants,
+        E::List: AllVariantsHaveField<E, NAME>,
+    {
+    }
+
+    pub const fn all_variants_have_field_of_type<E, U, const NAME: u64>()
+    where
+        E: ?Sized + Variants,
+        U: ?Sized,
+        E::List: AllVariantsHaveFieldOfType<E, NAME, U>,
+    {
+    }
+
+    /// Implemented by `#[derive(HasField)]` for every field, including the ones without
+    /// a [`HasField`](crate::HasField) implementation. Unlike an associated type, the type parameter
+    /// can be private even if the struct is public.
+    pub trait DeclaresField<const NAME: u64, T: ?Sized> {}
+
+    // Source: https://github.com/nvzqz/static-assertions/blob/18bc65a094d890fe1faa5d3ccb70f12b89eabf56/src/assert_impl.rs#L288
+    // `<T as LacksField<NAME, _>>::some_item` is ambiguous if and only if `T` has the field.
+    pub trait LacksField<const NAME: u64, A> {
+        fn some_item() {}
+    }
+    impl<T: ?Sized, const NAME: u64> LacksField<NAME, ()> for T {}
+    impl<T, U, const NAME: u64> LacksField<NAME, *const U> for T
+    where
+        T: ?Sized + DeclaresField<NAME, U>,
+        U: ?Sized,
+    {
+    }
+
+    // `<T as LacksFieldOfType<NAME, U, _>>::some_item` is ambiguous if and only if `T` has the field of type `U`.
+    pub trait LacksFieldOfType<const NAME: u64, U: ?Sized, A> {
+        fn some_item() {}
+    }
+    impl<T: ?Sized, U: ?Sized, const NAME: u64> LacksFieldOfType<NAME, U, ()> for T {}
+    impl<T, U, const NAME: u64> LacksFieldOfType<NAME, U, u8> for T
     where
-        T: IsEqual<U>,
+        T: ?Sized + DeclaresField<NAME, U>,
+        U: ?Sized,
     {
     }
 }
 
+/// The type-level counterpart of a field access, implemented by `#[derive(HasField)]`
+/// for every field of a struct or union.
+///
+/// For a `pub` struct, only the `pub` fields implement `HasField`, because the private fields
+/// may be of private types, which the implementations for a public type can't name.
+/// [`assert_lacks_field!`] still sees every field.
+///
+/// The field is identified by its [`field_id`], i.e. `HasField<{ field_id("x") }>` for a field `x`
+/// and `HasField<{ field_id("0") }>` for the first field of a tuple struct.
+/// The type of a field can be named with [`field_type`], and the field itself is accessed through [`GetField`].
+///
+/// ## Examples
+///
+/// Unlike [`GetField`], `HasField` is also derived for `#[repr(packed)]` structs and unions,
+/// so that [`assert_lacks_field!`] can check them.
+///
+/// ```rust
+/// use assert_has_field::{assert_lacks_field, HasField};
+///
+/// #[allow(dead_code)]
+/// #[derive(HasField)]
+/// #[repr(C, packed)]
+/// struct Frame {
+///     kind: u8,
+///     len: u32,
+/// }
+///
+/// assert_lacks_field!(Frame, checksum);
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::{field_id, GetField, HasField};
+///
+/// #[derive(HasField)]
+/// #[repr(C, packed)]
+/// struct Frame {
+///     kind: u8,
+///     len: u32,
+/// }
+///
+/// let frame = Frame { kind: 1, len: 2 };
+/// // This will cause a compile-time error because the fields of `Frame` can't be referenced.
+/// let _ = GetField::<{ field_id("len") }>::get(&frame);
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` doesn't have the field identified by `{NAME}`",
+    label = "the fields are only known for the types with `#[derive(HasField)]`"
+)]
+pub trait HasField<const NAME: u64> {
+    /// The type of the field.
+    type Type: ?Sized;
+}
+
+/// The accessors of a field, implemented by `#[derive(HasField)]` for the `pub` fields of a struct
+/// unless it is `#[repr(packed)]`.
+///
+/// Since the accessors can be called wherever the struct is visible, a private field only gets them
+/// when it is marked with `#[has_field(get)]`. For a `pub` struct, this also exposes the type of the field
+/// through [`HasField`](trait@HasField), so the type must be public.
+///
+/// ## Examples
+///
+/// ```rust
+/// use assert_has_field::{assert_has_field, field_id, GetField, HasField};
+///
+/// #[derive(HasField)]
+/// struct Command {
+///     pub request_id: u64,
+///     #[has_field(get)]
+///     retries: u8,
+/// }
+///
+/// fn log_id<T>(value: &T) -> u64
+/// where
+///     T: GetField<{ field_id("request_id") }, Type = u64>,
+/// {
+///     // The same contract as for the concrete types, checked through the `GetField` bound.
+///     assert_has_field!(let derived T, request_id: u64);
+///     *value.get()
+/// }
+///
+/// let mut command = Command { request_id: 42, retries: 0 };
+/// *GetField::<{ field_id("retries") }>::get_mut(&mut command) += 1;
+/// assert_eq!(log_id(&command), 42);
+/// assert_eq!(command.retries, 1);
+/// ```
+///
+/// ```rust,compile_fail
+/// mod account {
+///     use assert_has_field::HasField;
+///
+///     #[derive(HasField)]
+///     pub struct Account {
+///         pub owner: &'static str,
+///         balance: u64,
+///     }
+///
+///     pub fn open(owner: &'static str) -> Account {
+///         Account { owner, balance: 0 }
+///     }
+/// }
+///
+/// use assert_has_field::{field_id, GetField};
+///
+/// let mut account = account::open("alice");
+/// *GetField::<{ field_id("owner") }>::get_mut(&mut account) = "bob";
+/// // This will cause a compile-time error because `balance` is private and not marked with `#[has_field(get)]`.
+/// *GetField::<{ field_id("balance") }>::get_mut(&mut account) = 100;
+/// ```
+///
+/// In the `derived` mode of [`assert_has_field!`], a nested tuple index is written with a space, e.g. `1 .0`,
+/// because `1.0` is lexed as a single float literal.
+///
+/// ```rust,compile_fail
+/// use assert_has_field::{assert_has_field, HasField};
+///
+/// #[allow(dead_code)]
+/// #[derive(HasField)]
+/// struct Pair(pub u8, pub u16);
+///
+/// #[allow(dead_code)]
+/// #[derive(HasField)]
+/// struct Pairs(pub u8, pub Pair);
+///
+/// assert_has_field!(derived Pairs, 1 .1: u16);
+/// // This will cause a compile-time error because `1.1` is lexed as a float literal.
+/// assert_has_field!(derived Pairs, 1.1: u16);
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "the field of `{Self}` identified by `{NAME}` can't be accessed",
+    label = "the fields are only accessible if they are `pub` or marked with `#[has_field(get)]` in a struct with `#[derive(HasField)]` that isn't `#[repr(packed)]`"
+)]
+pub trait GetField<const NAME: u64>: HasField<NAME> {
+    /// Returns a reference to the field.
+    fn get(&self) -> &Self::Type;
+
+    /// Returns a mutable reference to the field.
+    fn get_mut(&mut self) -> &mut Self::Type;
+}
+
+/// Computes the identifier of the field with the given name, which is used as the
+/// const generic argument of [`HasField`](trait@HasField).
+///
+/// The identifier is the 64-bit FNV-1a hash of the name.
+pub const fn field_id(name: &str) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let bytes = name.as_bytes();
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+    hash
+}
+
 /// This macro performs a compile-time check if a struct has a specific field.
 ///
 /// ## Syntax
 ///
-/// The macro offers three syntaxes for checking if a struct has a field
+/// The macro offers seven syntaxes for checking if a struct has a field
 ///
 /// 1. `assert_has_field!(Struct, field);` - checks if the struct has a field with the given name.
 /// 2. `assert_has_field!(Struct, field: Type);` - checks if the struct has a field with the given name and type.
 /// 3. `assert_has_field!(Struct, field :~ Type);` - checks if the struct has a field with the given name and type that can be coerced to the specified type `Type`.
+/// 4. `assert_has_field!(Struct, field: impl Trait + 'static);` - checks if the struct has a field with the given name whose type satisfies the given bounds.
+/// 5. `assert_has_field!(Struct, field: !Type);` - checks if the struct has a field with the given name and a type other than `Type`.
+/// 6. `assert_has_field!(Struct, field :> Type);` - checks if the struct has a field with the given name whose type implements `Into<Type>`.
+/// 7. `assert_has_field!(Struct, field :?> Type);` - checks if the struct has a field with the given name whose type implements `TryInto<Type>`.
+///
+/// The field can be either a name or, for tuple structs and tuple types, a numeric index.
+/// Nested fields can be reached with a dotted path, e.g. `assert_has_field!(Struct, a.b.0: Type);`,
+/// in which case the type assertion applies to the last field of the path.
+///
+/// The bounds of the `impl` syntax may be higher-ranked, e.g. `impl for<'a> Fn(&'a str) -> bool`,
+/// and don't require the type of the field to be sized. They can't name the generic parameters
+/// introduced by the `for<...>` binder or those of the enclosing scope.
+///
+/// The types of the `:` and `:~` syntaxes may contain `_` placeholders, e.g. `cache: HashMap<String, _>`,
+/// in which case only the named parts of the type are checked.
+///
+/// The `!` syntax fails with a "type annotations needed" error when the types are equal. For generic
+/// fields, it only fails when the types are equal for every instantiation.
+///
+/// The variance of a field in a lifetime of the struct is checked with `field: covariant in 'a` or
+/// `field: contravariant in 'a`, where the lifetime is introduced by the `for<...>` binder, e.g.
+/// `assert_has_field!(for<'a> Ref<'a>, name: covariant in 'a);`. The other generic arguments
+/// of the struct must be concrete, and a path through [`Deref`](core::ops::Deref) requires
+/// [`DerefMut`](core::ops::DerefMut). The variance of the whole type is checked with `assert_variance!`.
+///
+/// Several fields of the same struct can be checked at once by listing them in braces,
+/// e.g. `assert_has_field!(Struct, { a, b: Type, c :~ Type });`. The syntaxes can be mixed freely.
+///
+/// Several structs can be checked for the same fields at once by listing them in brackets,
+/// e.g. `assert_has_field!([User, Order, Invoice], id: u64);`. Every struct is checked separately,
+/// so the errors of the missing fields and of the `:`, `!`, `:>`, and `:?>` syntaxes name the struct
+/// that failed. The errors of the `:~` and `impl` syntaxes couldn't name it, so these syntaxes are rejected
+/// in the list. The list can't be combined with the `const` prefix or with enum variants.
+///
+/// By default, just like the field access expressions, the macro finds the fields through
+/// [`Deref`](core::ops::Deref). Prefixing the struct with `strict`, e.g.
+/// `assert_has_field!(strict Struct, field);`, requires the field to be declared on the struct itself.
+/// Prefixing it with `deref` spells out the default behavior. The `strict` mode relies on
+/// [`offset_of!`](core::mem::offset_of) and therefore cannot check the trailing unsized field
+/// of an unsized struct.
+///
+/// Prefixing the struct with `derived`, e.g. `assert_has_field!(derived Struct, field);`, finds the fields
+/// through the [`GetField`] implementations instead, which requires the structs on the path
+/// to derive `HasField` and the private fields to be marked with `#[has_field(get)]` but, unlike the field access expressions, also works for the generic parameters
+/// bounded by `GetField`, e.g. `assert_has_field!(let derived T, request_id: u64);`. Just like for
+/// the enum variants, a nested tuple index must be separated from the preceding index with a space, e.g. `1 .0`.
+///
+/// Generic structs can be checked for all instantiations at once by introducing the generic
+/// parameters with a `for<...>` binder, e.g. `assert_has_field!(for<T: Clone> Wrapper<T>, inner: T);`.
+/// The binder follows the `strict`, `deref`, or `derived` prefix, if any.
+///
+/// By default, the macro expands to an item, which cannot use the generic parameters or `Self`
+/// of the enclosing function or impl block. Prefixing the assertion with `let`, e.g.
+/// `assert_has_field!(let Self, id: u64);`, expands it to a statement instead, which is also
+/// usable in expression position. Within an inherent impl block, the assertion can be expanded to an
+/// associated constant with the given name, e.g. `assert_has_field!(const ASSERT_ID for Self, id: u64);`.
+/// These prefixes go before the `strict`, `deref`, or `derived` prefix, if any, and don't support the `for<...>` binder.
+///
+/// The fields of an enum variant are checked by prefixing the variant path with `enum`, e.g.
+/// `assert_has_field!(enum Event::Created, id: u64);` or `assert_has_field!(enum Event::Deleted, 0: u64);`.
+/// The generic arguments of the enum must be spelled out, e.g. `enum Event<u64>::Created`, and the
+/// `strict` mode is not supported because the variants have no field access expressions.
+/// A nested path into a field of a tuple-like variant must separate the leading index from the
+/// rest of the path with a space, e.g. `1 .0`, because `1.0` is lexed as a single float literal
+/// that cannot be used in a pattern.
+///
+/// The fields shared by all variants of an enum are checked with `enum Enum::*`, e.g.
+/// `assert_has_field!(enum Command::*, request_id: RequestId);`. Since a macro can't list
+/// the variants of an enum, the enum must derive `HasField`, which is available with the `derive` feature.
+/// Only single fields are supported, either without a type or with the `:` type assertion.
 ///
 /// ## Examples
 ///
@@ -137,91 +596,2444 @@ pub mod secret {
 /// assert_has_field!(Point2, x :~ &'static u64);
 /// ```
 ///
-/// ## On real use-cases
+/// Tuple structs and tuple types are supported with numeric field indices in all three syntaxes.
 ///
-/// Let's say that you're writing a backend server and have a DTO, which is meant
-/// to be used on the frontend. Assume that this DTO aggregates different kinds of
-/// data that pertains to a candidate. You may be in a situation where `candidate_id`
-/// is stored in one of the fields-structures. You can use [`assert_has_field`] to
-/// document that expectation and future-proof the type in case the field-structure
-/// that used to store `candidate_id` is removed entirely or modified in a way that
-/// moves or removes the `candidate_id`.
-#[macro_export]
-macro_rules! assert_has_field {
-    (@ASSERT $unreachable_obj:ident: $struct:ty, $field:ident) => {
-        // Here, it is only checked that the field exists.
-        let _: _ = $unreachable_obj.$field;
-    };
-    (@ASSERT $unreachable_obj:ident: $struct:ty, $field:ident : $field_ty:ty) => {
-        // We define a dummy function instead of calling the function directly
-        // because the function call would be non-constant
-        //
-        // At the moment of writing, a non-constant function call falsly compiled but oh well
-        fn dummy(v: $struct) {
-            $crate::secret::ty_must_eq::<_, $field_ty>(
-                // Here, the validation that the field exists is performed
-                v.$field
-            );
-        }
-    };
-    (@ASSERT $unreachable_obj:ident: $struct:ty, $field:ident :~ $field_ty:ty) => {
-        // Here, the value on the right hand side can be coerced to the type on the left hand side
-        // and the field must exist.
-        let _ : $field_ty = $unreachable_obj.$field;
-    };
-    (
-        $struct:ty,
-        $field:ident
-            $($rest:tt)*
-    ) => {
-        // The const block forces the const evaluation.
-        #[allow(
-            unreachable_code,
-            unused_variables,
-            clippy::diverging_sub_expression,
-        )]
-        const _: () = {
-            // `if false { ... }` ensures that the unreacahble! macro invokation is indeed unreachable.
-            if false {
-                // Rust performs the type-checking at compile time even if the code is unreachable.
-                //
-                // The return type of core::unreachable!() is never type,
-                // which can be assigned to any type.
-                let unreachable_obj: $struct = core::unreachable!();
-                assert_has_field!(@ASSERT unreachable_obj: $struct, $field $($rest)*);
-            }
-        };
-    };
-}
-
-#[cfg(test)]
-mod tests {
-    use super::assert_has_field;
-
-    #[allow(dead_code)]
-    struct Point {
-        x: u64,
-        y: u64,
-    }
-
-    assert_has_field!(Point, x);
-    assert_has_field!(Point, x : u64);
-
-    struct Wrapper<T>(T);
-
-    impl<T> core::ops::Deref for Wrapper<T> {
-        type Target = T;
-
-        fn deref(&self) -> &Self::Target {
-            &self.0
-        }
-    }
-
-    #[allow(dead_code)]
-    struct Point2 {
-        x: &'static Wrapper<u64>,
-        y: u64,
-    }
-
-    assert_has_field!(Point2, x :~ &'static u64);
+/// ```rust
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Wrapper<T>(T);
+///
+/// assert_has_field!(Wrapper<u64>, 0);
+/// assert_has_field!(Wrapper<u64>, 0: u64);
+/// assert_has_field!((u8, &'static Wrapper<u64>), 1 :~ &'static Wrapper<u64>);
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_has_field;
+///
+/// // This will cause a compile-time error because the tuple has only two fields.
+/// assert_has_field!((u8, u16), 2);
+/// ```
+///
+/// Nested fields are checked with dotted paths, which can mix field names and tuple indices.
+///
+/// ```rust
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Ids {
+///     candidate_id: u64,
+/// }
+///
+/// #[allow(dead_code)]
+/// struct Profile {
+///     ids: Ids,
+///     names: (&'static str, &'static str),
+/// }
+///
+/// #[allow(dead_code)]
+/// struct CandidateDto {
+///     profile: Profile,
+/// }
+///
+/// assert_has_field!(CandidateDto, profile.ids.candidate_id);
+/// assert_has_field!(CandidateDto, profile.ids.candidate_id: u64);
+/// assert_has_field!(CandidateDto, profile.names.1 :~ &'static str);
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Ids {
+///     candidate_id: u64,
+/// }
+///
+/// #[allow(dead_code)]
+/// struct CandidateDto {
+///     ids: Ids,
+/// }
+///
+/// // This will cause a compile-time error because the type assertion applies to `ids.candidate_id`.
+/// assert_has_field!(CandidateDto, ids.candidate_id: Ids);
+/// ```
+///
+/// Long contracts can be expressed with a single invocation that lists the fields in braces.
+///
+/// ```rust
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Point {
+///     x: u64,
+///     y: u64,
+///     label: String,
+///     tag: &'static &'static str,
+/// }
+///
+/// assert_has_field!(Point, {
+///     x: u64,
+///     y: u64,
+///     label,
+///     tag :~ &'static str,
+/// });
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Point {
+///     x: u64,
+///     y: u64,
+/// }
+///
+/// // This will cause a compile-time error because `Point` does not have a field `z`.
+/// assert_has_field!(Point, { x: u64, y: u64, z: u64 });
+/// ```
+///
+/// Since field access expressions go through [`Deref`](core::ops::Deref), so does the macro by default.
+/// The `strict` mode only accepts the fields declared directly on the type.
+///
+/// ```rust
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Point {
+///     x: u64,
+///     y: u64,
+/// }
+///
+/// // `Box<Point>` derefs to `Point`, which has a field `x`.
+/// assert_has_field!(Box<Point>, x: u64);
+/// // The same as above, but with the deref-following behavior made explicit.
+/// assert_has_field!(deref Box<Point>, x: u64);
+/// // `Point` itself declares `x`.
+/// assert_has_field!(strict Point, x: u64);
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Point {
+///     x: u64,
+///     y: u64,
+/// }
+///
+/// // This will cause a compile-time error because `Box<Point>` does not declare a field `x`.
+/// assert_has_field!(strict Box<Point>, x);
+/// ```
+///
+/// The fields are never moved out of the struct, so the structs that implement [`Drop`]
+/// are supported in all three syntaxes.
+///
+/// ```rust
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Connection {
+///     address: String,
+///     buffer: Vec<u8>,
+/// }
+///
+/// impl Drop for Connection {
+///     fn drop(&mut self) {}
+/// }
+///
+/// assert_has_field!(Connection, {
+///     address: String,
+///     buffer :~ Vec<u8>,
+/// });
+/// ```
+///
+/// Neither are the fields referenced, so `#[repr(packed)]` structs are supported as well.
+///
+/// ```rust
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// #[repr(C, packed)]
+/// struct Frame {
+///     kind: u8,
+///     len: u32,
+///     payload: Vec<u8>,
+/// }
+///
+/// assert_has_field!(Frame, {
+///     kind: u8,
+///     len :~ u32,
+///     payload: Vec<u8>,
+///     payload :~ Vec<u8>,
+/// });
+/// ```
+///
+/// The struct doesn't have to be [`Sized`], and the type of its trailing unsized field
+/// can be asserted as well.
+///
+/// ```rust
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Packet {
+///     len: u16,
+///     data: [u8],
+/// }
+///
+/// #[allow(dead_code)]
+/// struct Named<T: ?Sized> {
+///     id: u32,
+///     value: T,
+/// }
+///
+/// assert_has_field!(Packet, { len: u16, data: [u8] });
+/// assert_has_field!(Named<dyn core::fmt::Debug>, {
+///     id :~ u32,
+///     value: dyn core::fmt::Debug,
+/// });
+/// ```
+///
+/// With a `for<...>` binder, the assertion is checked for every instantiation of the generic
+/// parameters, which can be types (with bounds), lifetimes, or constants.
+///
+/// ```rust
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Wrapper<T> {
+///     inner: T,
+/// }
+///
+/// #[allow(dead_code)]
+/// struct Ref<'a> {
+///     r: &'a str,
+/// }
+///
+/// #[allow(dead_code)]
+/// struct Buf<const N: usize> {
+///     data: [u8; N],
+/// }
+///
+/// assert_has_field!(for<T> Wrapper<T>, inner: T);
+/// assert_has_field!(for<T: Clone + Into<Vec<u8>>> Wrapper<Vec<T>>, inner: Vec<T>);
+/// assert_has_field!(for<'a> Ref<'a>, r: &'a str);
+/// assert_has_field!(strict for<const N: usize> Buf<N>, data: [u8; N]);
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Wrapper<T> {
+///     inner: T,
+/// }
+///
+/// // This will cause a compile-time error because `Wrapper<T>`'s field `inner` is of type `T`, not `u64`.
+/// assert_has_field!(for<T> Wrapper<T>, inner: u64);
+/// ```
+///
+/// The `let` prefix makes the assertion a statement that can refer to the generic parameters
+/// and `Self` of the enclosing function or impl block.
+///
+/// ```rust
+/// use assert_has_field::assert_has_field;
+///
+/// trait Entity {
+///     type Row;
+/// }
+///
+/// #[allow(dead_code)]
+/// struct Repo<T> {
+///     id: u64,
+///     items: Vec<T>,
+/// }
+///
+/// impl<T> Repo<T> {
+///     fn new() -> Self {
+///         assert_has_field!(let strict Self, { id: u64, items: Vec<T> });
+///         Self { id: 0, items: Vec::new() }
+///     }
+/// }
+///
+/// #[allow(dead_code)]
+/// fn load<E: Entity<Row = (u64, String)>>() {
+///     assert_has_field!(let E::Row, { 0: u64, 1 :~ String });
+/// }
+/// ```
+///
+/// Within an inherent impl block, the assertion can be an associated constant instead.
+///
+/// ```rust
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Repo<T> {
+///     id: u64,
+///     items: Vec<T>,
+/// }
+///
+/// impl<T> Repo<T> {
+///     assert_has_field!(const ASSERT_FIELDS for Self, { id: u64, items: Vec<T> });
+/// }
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Repo<T> {
+///     id: u64,
+///     items: Vec<T>,
+/// }
+///
+/// impl<T> Repo<T> {
+///     // This will cause a compile-time error because `Repo<T>`'s field `items` is of type `Vec<T>`, not `T`.
+///     assert_has_field!(const ASSERT_ITEMS for Self, items: T);
+/// }
+/// ```
+///
+/// The type of a field can be checked against trait bounds instead of a specific type.
+///
+/// ```rust
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Handler {
+///     name: &'static str,
+///     on_event: fn(&str) -> bool,
+/// }
+///
+/// assert_has_field!(Handler, {
+///     name: impl Clone + Send + 'static,
+///     on_event: impl for<'a> Fn(&'a str) -> bool,
+/// });
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Handler {
+///     name: String,
+/// }
+///
+/// // This will cause a compile-time error because `String` is not `Copy`.
+/// assert_has_field!(Handler, name: impl Copy);
+/// ```
+///
+/// The parts of the type that don't matter can be left out with `_` placeholders.
+///
+/// ```rust
+/// extern crate alloc;
+///
+/// use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Service {
+///     cache: BTreeMap<String, Vec<u8>>,
+///     handler: Box<dyn Fn(u64) -> bool>,
+/// }
+///
+/// assert_has_field!(Service, {
+///     cache: BTreeMap<String, _>,
+///     handler: Box<dyn Fn(_) -> bool>,
+/// });
+/// ```
+///
+/// ```rust,compile_fail
+/// extern crate alloc;
+///
+/// use alloc::{collections::BTreeMap, string::String, vec::Vec};
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Service {
+///     cache: BTreeMap<u64, Vec<u8>>,
+/// }
+///
+/// // This will cause a compile-time error because the keys of the cache are not `String`s.
+/// assert_has_field!(Service, cache: BTreeMap<String, _>);
+/// ```
+///
+/// A field can be required to be of any type but a specific one.
+///
+/// ```rust
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Secret<T>(T);
+///
+/// #[allow(dead_code)]
+/// struct User {
+///     password: Secret<&'static str>,
+///     amount: u64,
+/// }
+///
+/// assert_has_field!(User, { password: !&'static str, amount: !f64 });
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct User {
+///     password: &'static str,
+/// }
+///
+/// // This will cause a compile-time error because the password is a bare `&'static str`.
+/// assert_has_field!(User, password: !&'static str);
+/// ```
+///
+/// Unlike the coercions of the `:~` syntax, the `:>` and `:?>` syntaxes check the conversions
+/// through the `Into` and `TryInto` traits.
+///
+/// ```rust
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct UserId(u128);
+///
+/// impl From<UserId> for u128 {
+///     fn from(id: UserId) -> u128 {
+///         id.0
+///     }
+/// }
+///
+/// #[allow(dead_code)]
+/// struct User {
+///     id: UserId,
+///     age: u64,
+/// }
+///
+/// assert_has_field!(User, { id :> u128, age :?> u8 });
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct User {
+///     age: u64,
+/// }
+///
+/// // This will cause a compile-time error because `u64` doesn't implement `Into<u8>`.
+/// assert_has_field!(User, age :> u8);
+/// ```
+///
+/// Enum variants are checked with pattern matching, which works for both struct-like
+/// and tuple-like variants.
+///
+/// ```rust
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Timestamp(u64);
+///
+/// #[allow(dead_code)]
+/// enum Event<Id> {
+///     Created { id: Id, at: Timestamp },
+///     Deleted(Id),
+/// }
+///
+/// assert_has_field!(enum Event<u128>::Created, { id: u128, at.0: u64 });
+/// assert_has_field!(enum Event<u128>::Deleted, 0 :~ u128);
+/// assert_has_field!(for<Id> enum Event::<Id>::Deleted, 0: Id);
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// enum Event {
+///     Created { id: u128 },
+///     Deleted(u128),
+/// }
+///
+/// // This will cause a compile-time error because the variant `Event::Deleted` has no field `id`.
+/// assert_has_field!(enum Event::Deleted, id: u128);
+/// ```
+///
+/// Every variant of an enum can be required to have a field, including the variants added later.
+///
+/// ```rust
+/// use assert_has_field::{assert_has_field, HasField};
+///
+/// #[allow(dead_code)]
+/// struct RequestId(u64);
+///
+/// #[allow(dead_code)]
+/// #[derive(HasField)]
+/// enum Command {
+///     Create { request_id: RequestId, name: &'static str },
+///     Delete { request_id: RequestId },
+/// }
+///
+/// assert_has_field!(enum Command::*, request_id: RequestId);
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::{assert_has_field, HasField};
+///
+/// #[allow(dead_code)]
+/// struct RequestId(u64);
+///
+/// #[allow(dead_code)]
+/// #[derive(HasField)]
+/// enum Command {
+///     Create { request_id: RequestId, name: &'static str },
+///     Delete { request_id: RequestId },
+///     Ping,
+/// }
+///
+/// // This will cause a compile-time error naming the variant `Ping`.
+/// assert_has_field!(enum Command::*, request_id: RequestId);
+/// ```
+///
+/// ```rust
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct User {
+///     id: u64,
+///     name: String,
+/// }
+///
+/// #[allow(dead_code)]
+/// struct Order {
+///     id: u64,
+///     total: u64,
+/// }
+///
+/// assert_has_field!([User, Order], id: u64);
+/// assert_has_field!(strict [User, Order], { id, id: !u32 });
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct User {
+///     id: u64,
+/// }
+///
+/// #[allow(dead_code)]
+/// struct Invoice {
+///     id: u32,
+/// }
+///
+/// // This will cause a compile-time error because `Invoice`'s field `id` is of type `u32`, not `u64`.
+/// assert_has_field!([User, Invoice], id: u64);
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct User {
+///     id: u8,
+/// }
+///
+/// #[allow(dead_code)]
+/// struct Invoice {
+///     id: u32,
+/// }
+///
+/// // This will cause a compile-time error pointing at `Invoice` because `u32` doesn't implement `Into<u16>`.
+/// assert_has_field!([User, Invoice], id :> u16);
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct User {
+///     id: u8,
+/// }
+///
+/// #[allow(dead_code)]
+/// struct Invoice {
+///     id: u32,
+/// }
+///
+/// // This will cause a compile-time error because the `:~` syntax can't be used with a list of structs.
+/// assert_has_field!([User, Invoice], id :~ u64);
+/// ```
+///
+/// ## On real use-cases
+///
+/// Let's say that you're writing a backend server and have a DTO, which is meant
+/// to be used on the frontend. Assume that this DTO aggregates different kinds of
+/// data that pertains to a candidate. You may be in a situation where `candidate_id`
+/// is stored in one of the fields-structures. You can use [`assert_has_field!`] to
+/// document that expectation and future-proof the type in case the field-structure
+/// that used to store `candidate_id` is removed entirely or modified in a way that
+/// moves or removes the `candidate_id`.
+#[macro_export]
+macro_rules! assert_has_field {
+    (@ASSERT $mode:ident $struct_ref:ident: $struct:ty;) => {};
+    (@ASSERT $mode:ident $struct_ref:ident: $struct:ty; $($field:tt).+ : covariant in $lt:lifetime $(, $($rest:tt)*)?) => {
+        // Here, the field type must be covariant in the lifetime and the field must exist.
+        //
+        // The regions of the local variables are inferred, so the subtyping is checked against
+        // the field of the mutable parameter, whose type is fixed by the signature.
+        {
+            type Alias<$lt> = $struct;
+
+            fn covariant<'short, 'long: 'short>(short: &mut Alias<'short>, long: &Alias<'long>) {
+                $crate::secret::must_be_subtype(&raw mut (*short) $(.$field)+, &raw const (*long) $(.$field)+);
+            }
+        }
+        let _ = &raw const (*$struct_ref) $(.$field)+;
+        $crate::assert_has_field!(@OWNS $mode $struct, $($field).+);
+        $crate::assert_has_field!(@ASSERT $mode $struct_ref: $struct; $($($rest)*)?);
+    };
+    (@ASSERT $mode:ident $struct_ref:ident: $struct:ty; $($field:tt).+ : contravariant in $lt:lifetime $(, $($rest:tt)*)?) => {
+        // Here, the field type must be contravariant in the lifetime and the field must exist.
+        {
+            type Alias<$lt> = $struct;
+
+            fn contravariant<'short, 'long: 'short>(short: &Alias<'short>, long: &mut Alias<'long>) {
+                $crate::secret::must_be_subtype(&raw mut (*long) $(.$field)+, &raw const (*short) $(.$field)+);
+            }
+        }
+        let _ = &raw const (*$struct_ref) $(.$field)+;
+        $crate::assert_has_field!(@OWNS $mode $struct, $($field).+);
+        $crate::assert_has_field!(@ASSERT $mode $struct_ref: $struct; $($($rest)*)?);
+    };
+    (@ASSERT $mode:ident $struct_ref:ident: $struct:ty; $($field:tt).+ :> $field_ty:ty $(, $($rest:tt)*)?) => {
+        // Here, the field type must implement `Into` the type argument
+        // and the field must exist.
+        $crate::secret::field_must_convert_into::<$struct, _, $field_ty>($crate::assert_has_field!(@PROJECT $mode $struct_ref; $($field).+));
+        $crate::assert_has_field!(@OWNS $mode $struct, $($field).+);
+        $crate::assert_has_field!(@ASSERT $mode $struct_ref: $struct; $($($rest)*)?);
+    };
+    (@ASSERT $mode:ident $struct_ref:ident: $struct:ty; $($field:tt).+ :?> $field_ty:ty $(, $($rest:tt)*)?) => {
+        // Here, the field type must implement `TryInto` the type argument
+        // and the field must exist.
+        $crate::secret::field_must_try_convert_into::<$struct, _, $field_ty>($crate::assert_has_field!(@PROJECT $mode $struct_ref; $($field).+));
+        $crate::assert_has_field!(@OWNS $mode $struct, $($field).+);
+        $crate::assert_has_field!(@ASSERT $mode $struct_ref: $struct; $($($rest)*)?);
+    };
+    (@ASSERT $mode:ident $struct_ref:ident: $struct:ty; $($field:tt).+ : ! $field_ty:ty $(, $($rest:tt)*)?) => {
+        // Here, the field must be of a type other than the type argument
+        // and the field must exist.
+        $crate::secret::field_must_not_be_of_type::<$struct, _, $field_ty, _>($crate::assert_has_field!(@PROJECT $mode $struct_ref; $($field).+));
+        $crate::assert_has_field!(@OWNS $mode $struct, $($field).+);
+        $crate::assert_has_field!(@ASSERT $mode $struct_ref: $struct; $($($rest)*)?);
+    };
+    (@ASSERT $mode:ident listed_ref: $struct:ty; $($field:tt).+ : impl $($rest:tt)+) => {
+        ::core::compile_error!("the `impl` syntax can't be used with a list of structs, because its errors can't name the struct");
+    };
+    (@ASSERT $mode:ident listed_ref: $struct:ty; $($field:tt).+ :~ $($rest:tt)+) => {
+        ::core::compile_error!("the `:~` syntax can't be used with a list of structs, because its errors can't name the struct");
+    };
+    (@ASSERT $mode:ident $struct_ref:ident: $struct:ty; $($field:tt).+ : impl $($rest:tt)+) => {
+        $crate::assert_has_field!(@BOUNDS [@IMPL $mode $struct_ref: $struct; $($field).+] [] [] $($rest)+);
+    };
+    (@IMPL $mode:ident $struct_ref:ident: $struct:ty; $($field:tt).+ [$($bounds:tt)+] $($rest:tt)*) => {
+        // Here, the type of the field must satisfy the bounds and the field must exist.
+        //
+        // The bounds are checked by a nested function, so they can't name the generic parameters
+        // of the `for<...>` binder or of the enclosing scope. The type parameter of the function
+        // is anonymous, so that it can't be shadowed by the names used in the bounds.
+        {
+            fn must_impl(_: *const (impl ?Sized + $($bounds)+)) {}
+            must_impl($crate::assert_has_field!(@PROJECT $mode $struct_ref; $($field).+));
+        }
+        $crate::assert_has_field!(@OWNS $mode $struct, $($field).+);
+        $crate::assert_has_field!(@ASSERT $mode $struct_ref: $struct; $($rest)*);
+    };
+    (@ASSERT $mode:ident $struct_ref:ident: $struct:ty; $($field:tt).+ : $field_ty:ty $(, $($rest:tt)*)?) => {
+        // Here, the field must be of the exact same type as the type argument
+        // and the field must exist.
+        //
+        // The pointer to the field doesn't move it out of the struct and, unlike a reference,
+        // it is allowed for the potentially unaligned fields of `#[repr(packed)]` structs.
+        $crate::secret::field_must_be_of_type::<$struct, _, $field_ty>($crate::assert_has_field!(@PROJECT $mode $struct_ref; $($field).+));
+        $crate::assert_has_field!(@OWNS $mode $struct, $($field).+);
+        $crate::assert_has_field!(@ASSERT $mode $struct_ref: $struct; $($($rest)*)?);
+    };
+    (@ASSERT $mode:ident $struct_ref:ident: $struct:ty; $($field:tt).+ :~ $field_ty:ty $(, $($rest:tt)*)?) => {
+        // Here, the value on the right hand side can be coerced to the type on the left hand side
+        // and the field must exist.
+        //
+        // The value of the field type is obtained without moving the field out of the struct
+        // and without referencing the potentially unaligned field of a `#[repr(packed)]` struct.
+        let value = $crate::secret::value_behind($crate::assert_has_field!(@PROJECT $mode $struct_ref; $($field).+));
+        let _ : $field_ty = value;
+        $crate::assert_has_field!(@OWNS $mode $struct, $($field).+);
+        $crate::assert_has_field!(@ASSERT $mode $struct_ref: $struct; $($($rest)*)?);
+    };
+    (@ASSERT $mode:ident $struct_ref:ident: $struct:ty; $($field:tt).+ $(, $($rest:tt)*)?) => {
+        // Here, it is only checked that the field exists.
+        let _ = $crate::assert_has_field!(@PROJECT $mode $struct_ref; $($field).+);
+        $crate::assert_has_field!(@OWNS $mode $struct, $($field).+);
+        $crate::assert_has_field!(@ASSERT $mode $struct_ref: $struct; $($($rest)*)?);
+    };
+    // Enum variants have no field projections, so their fields are bound by pattern matching.
+    // The type of the scrutinee is inferred from the pattern.
+    (@VARIANT $variant:path;) => {};
+    (@VARIANT $variant:path; $field:tt $(. $nested:tt)* :> $field_ty:ty $(, $($rest:tt)*)?) => {
+        match $crate::secret::unreachable_value() {
+            $variant { $field: ref variant_field, .. } => {
+                $crate::secret::ty_must_convert_into::<_, $field_ty>(&raw const (*variant_field) $(.$nested)*);
+            }
+            #[allow(unreachable_patterns)]
+            _ => {}
+        }
+        $crate::assert_has_field!(@VARIANT $variant; $($($rest)*)?);
+    };
+    (@VARIANT $variant:path; $field:tt $(. $nested:tt)* :?> $field_ty:ty $(, $($rest:tt)*)?) => {
+        match $crate::secret::unreachable_value() {
+            $variant { $field: ref variant_field, .. } => {
+                $crate::secret::ty_must_try_convert_into::<_, $field_ty>(&raw const (*variant_field) $(.$nested)*);
+            }
+            #[allow(unreachable_patterns)]
+            _ => {}
+        }
+        $crate::assert_has_field!(@VARIANT $variant; $($($rest)*)?);
+    };
+    (@VARIANT $variant:path; $field:tt $(. $nested:tt)* : ! $field_ty:ty $(, $($rest:tt)*)?) => {
+        match $crate::secret::unreachable_value() {
+            $variant { $field: ref variant_field, .. } => {
+                $crate::secret::ty_must_not_eq::<_, $field_ty, _>(&raw const (*variant_field) $(.$nested)*);
+            }
+            #[allow(unreachable_patterns)]
+            _ => {}
+        }
+        $crate::assert_has_field!(@VARIANT $variant; $($($rest)*)?);
+    };
+    (@VARIANT $variant:path; $field:tt $(. $nested:tt)* : impl $($rest:tt)+) => {
+        $crate::assert_has_field!(@BOUNDS [@VARIANT_IMPL $variant; $field $(.$nested)*] [] [] $($rest)+);
+    };
+    (@VARIANT_IMPL $variant:path; $field:tt $(. $nested:tt)* [$($bounds:tt)+] $($rest:tt)*) => {
+        match $crate::secret::unreachable_value() {
+            $variant { $field: ref variant_field, .. } => {
+                fn must_impl(_: *const (impl ?Sized + $($bounds)+)) {}
+                must_impl(&raw const (*variant_field) $(.$nested)*);
+            }
+            #[allow(unreachable_patterns)]
+            _ => {}
+        }
+        $crate::assert_has_field!(@VARIANT $variant; $($rest)*);
+    };
+    (@VARIANT $variant:path; $field:tt $(. $nested:tt)* : $field_ty:ty $(, $($rest:tt)*)?) => {
+        match $crate::secret::unreachable_value() {
+            $variant { $field: ref variant_field, .. } => {
+                $crate::secret::ty_must_eq::<_, $field_ty>(&raw const (*variant_field) $(.$nested)*);
+            }
+            #[allow(unreachable_patterns)]
+            _ => {}
+        }
+        $crate::assert_has_field!(@VARIANT $variant; $($($rest)*)?);
+    };
+    (@VARIANT $variant:path; $field:tt $(. $nested:tt)* :~ $field_ty:ty $(, $($rest:tt)*)?) => {
+        match $crate::secret::unreachable_value() {
+            $variant { $field: ref variant_field, .. } => {
+                let value = $crate::secret::value_behind(&raw const (*variant_field) $(.$nested)*);
+                let _ : $field_ty = value;
+            }
+            #[allow(unreachable_patterns)]
+            _ => {}
+        }
+        $crate::assert_has_field!(@VARIANT $variant; $($($rest)*)?);
+    };
+    (@VARIANT $variant:path; $field:tt $(. $nested:tt)* $(, $($rest:tt)*)?) => {
+        match $crate::secret::unreachable_value() {
+            $variant { $field: ref variant_field, .. } => {
+                let _ = &raw const (*variant_field) $(.$nested)*;
+            }
+            #[allow(unreachable_patterns)]
+            _ => {}
+        }
+        $crate::assert_has_field!(@VARIANT $variant; $($($rest)*)?);
+    };
+    // The variants are enumerated by `#[derive(HasField)]`, which implements `secret::Variants` for the enum.
+    (@ALL [$($enum:tt)+];) => {};
+    (@ALL [$($enum:tt)+]; $field:tt : $field_ty:ty $(, $($rest:tt)*)?) => {
+        $crate::secret::all_variants_have_field_of_type::<
+            $($enum)+,
+            $field_ty,
+            { $crate::secret::segment_id(::core::stringify!($field)) },
+        >();
+        $crate::assert_has_field!(@ALL [$($enum)+]; $($($rest)*)?);
+    };
+    (@ALL [$($enum:tt)+]; $field:tt $(, $($rest:tt)*)?) => {
+        $crate::secret::all_variants_have_field::<$($enum)+, { $crate::secret::segment_id(::core::stringify!($field)) }>();
+        $crate::assert_has_field!(@ALL [$($enum)+]; $($($rest)*)?);
+    };
+    // The bounds of `: impl Bounds` are collected up to the next comma outside of angle brackets
+    // and passed to the continuation along with the remaining items.
+    (@BOUNDS [$($cont:tt)+] [$($bounds:tt)*] []) => {
+        $crate::assert_has_field!($($cont)+ [$($bounds)*]);
+    };
+    (@BOUNDS [$($cont:tt)+] [$($bounds:tt)*] [] , $($rest:tt)*) => {
+        $crate::assert_has_field!($($cont)+ [$($bounds)*] $($rest)*);
+    };
+    (@BOUNDS [$($cont:tt)+] [$($bounds:tt)*] [$($depth:tt)*] < $($rest:tt)*) => {
+        $crate::assert_has_field!(@BOUNDS [$($cont)+] [$($bounds)* <] [< $($depth)*] $($rest)*);
+    };
+    (@BOUNDS [$($cont:tt)+] [$($bounds:tt)*] [$($depth:tt)*] << $($rest:tt)*) => {
+        $crate::assert_has_field!(@BOUNDS [$($cont)+] [$($bounds)*] [$($depth)*] < < $($rest)*);
+    };
+    (@BOUNDS [$($cont:tt)+] [$($bounds:tt)*] [$_:tt $($depth:tt)*] > $($rest:tt)*) => {
+        $crate::assert_has_field!(@BOUNDS [$($cont)+] [$($bounds)* >] [$($depth)*] $($rest)*);
+    };
+    (@BOUNDS [$($cont:tt)+] [$($bounds:tt)*] [$($depth:tt)*] >> $($rest:tt)*) => {
+        $crate::assert_has_field!(@BOUNDS [$($cont)+] [$($bounds)*] [$($depth)*] > > $($rest)*);
+    };
+    (@BOUNDS [$($cont:tt)+] [$($bounds:tt)*] [$($depth:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::assert_has_field!(@BOUNDS [$($cont)+] [$($bounds)* $token] [$($depth)*] $($rest)*);
+    };
+    // In the `derived` mode, the fields are found through the `GetField` implementations,
+    // which, unlike the field access expressions, are also available for the generic parameters.
+    (@PROJECT derived $struct_ref:ident; $($field:tt).+) => {{
+        let field = $struct_ref;
+        $(let field = $crate::GetField::<{ $crate::secret::segment_id(::core::stringify!($field)) }>::get(field);)+
+        ::core::ptr::from_ref(field)
+    }};
+    // Otherwise, the raw borrow of the field never moves or references the field.
+    (@PROJECT $mode:ident $struct_ref:ident; $($field:tt).+) => {
+        &raw const (*$struct_ref) $(.$field)+
+    };
+    // The `HasField` implementations are generated for the fields declared on the type itself.
+    (@OWNS derived $struct:ty, $($field:tt).+) => {};
+    (@OWNS deref $struct:ty, $($field:tt).+) => {};
+    (@OWNS strict $struct:ty, $($field:tt).+) => {
+        // Unlike the field access expressions, `offset_of!` never goes through `Deref`,
+        // so every field of the path must be declared directly on the type that precedes it.
+        //
+        // Since the field found by the field access expressions is the field declared
+        // directly on the type whenever there is one, the other checks are performed
+        // against the same field.
+        //
+        // Note that `offset_of!` doesn't accept unsized fields.
+        let _ = core::mem::offset_of!($struct, $($field).+);
+    };
+    // The generic parameters of the `for<...>` binder are collected token by token
+    // because they can't be matched with a fragment specifier. The second list tracks
+    // the nesting of angle brackets, which are not delimiters for `macro_rules!`.
+    (@GENERICS $scope:tt $mode:ident [$($generics:tt)*] [] > $($rest:tt)+) => {
+        $crate::assert_has_field!(@CHECK $scope $mode [$($generics)*] $($rest)+);
+    };
+    (@GENERICS $scope:tt $mode:ident [$($generics:tt)*] [$_:tt $($depth:tt)*] > $($rest:tt)+) => {
+        $crate::assert_has_field!(@GENERICS $scope $mode [$($generics)* >] [$($depth)*] $($rest)+);
+    };
+    (@GENERICS $scope:tt $mode:ident [$($generics:tt)*] [$($depth:tt)*] >> $($rest:tt)+) => {
+        $crate::assert_has_field!(@GENERICS $scope $mode [$($generics)*] [$($depth)*] > > $($rest)+);
+    };
+    (@GENERICS $scope:tt $mode:ident [$($generics:tt)*] [$($depth:tt)*] < $($rest:tt)+) => {
+        $crate::assert_has_field!(@GENERICS $scope $mode [$($generics)* <] [< $($depth)*] $($rest)+);
+    };
+    (@GENERICS $scope:tt $mode:ident [$($generics:tt)*] [$($depth:tt)*] << $($rest:tt)+) => {
+        $crate::assert_has_field!(@GENERICS $scope $mode [$($generics)*] [$($depth)*] < < $($rest)+);
+    };
+    (@GENERICS $scope:tt $mode:ident [$($generics:tt)*] [$($depth:tt)*] $token:tt $($rest:tt)+) => {
+        $crate::assert_has_field!(@GENERICS $scope $mode [$($generics)* $token] [$($depth)*] $($rest)+);
+    };
+    (@BINDER $scope:tt $mode:ident for < $($rest:tt)+) => {
+        $crate::assert_has_field!(@GENERICS $scope $mode [] [] $($rest)+);
+    };
+    (@BINDER $scope:tt $mode:ident $($rest:tt)+) => {
+        $crate::assert_has_field!(@CHECK $scope $mode [] $($rest)+);
+    };
+    // Every type of the list is checked separately, so the errors point at the type that failed.
+    (@CHECK $scope:tt $mode:ident $generics:tt [$($structs:tt)+], $($rest:tt)+) => {
+        $crate::assert_has_field!(@LIST $scope $mode $generics [$($structs)+] [$($rest)+]);
+    };
+    (@LIST $scope:tt $mode:ident $generics:tt [$struct:ty $(, $more:ty)* $(,)?] [$($rest:tt)+]) => {
+        $crate::assert_has_field!(@CHECK_LISTED $scope $mode $generics $struct, $($rest)+);
+        $crate::assert_has_field!(@LIST $scope $mode $generics [$($more),*] [$($rest)+]);
+    };
+    (@LIST $scope:tt $mode:ident $generics:tt [] [$($rest:tt)+]) => {};
+    (@CHECK_LISTED $scope:tt $mode:ident $generics:tt $struct:ty, { $($items:tt)* } $(,)?) => {
+        $crate::assert_has_field!(@CHECK_LISTED $scope $mode $generics $struct, $($items)*);
+    };
+    (@CHECK_LISTED $scope:tt $mode:ident [$($generics:tt)*] $struct:ty, $($rest:tt)+) => {
+        // Unlike `struct_ref`, the name of the reference tells the `@ASSERT` arms that the struct
+        // comes from a list, so that they can reject the syntaxes whose errors can't name the struct.
+        $crate::assert_has_field!(@EMIT $scope [$($generics)*] (listed_ref: &$struct) {
+            $crate::assert_has_field!(@ASSERT $mode listed_ref: $struct; $($rest)+);
+        });
+    };
+    (@CHECK $scope:tt $mode:ident [$($generics:tt)*] enum $($rest:tt)+) => {
+        $crate::assert_has_field!(@ENUM $scope $mode [$($generics)*] [] $($rest)+);
+    };
+    // `Enum::*` cannot be parsed as a path, so the tokens before the first field are scanned for `::*`
+    // before the subject is parsed as a variant path.
+    (@ENUM $scope:tt $mode:ident [$($generics:tt)*] [$($enum:tt)+] :: * , $($rest:tt)+) => {
+        $crate::assert_has_field!(@CHECK_ALL $scope $mode [$($generics)*] [$($enum)+], $($rest)+);
+    };
+    (@ENUM $scope:tt $mode:ident [$($generics:tt)*] [$($seen:tt)*] : $($rest:tt)*) => {
+        $crate::assert_has_field!(@CHECK_VARIANT $scope $mode [$($generics)*] $($seen)* : $($rest)*);
+    };
+    (@ENUM $scope:tt $mode:ident [$($generics:tt)*] [$($seen:tt)*] { $($items:tt)* } $($rest:tt)*) => {
+        $crate::assert_has_field!(@CHECK_VARIANT $scope $mode [$($generics)*] $($seen)* { $($items)* } $($rest)*);
+    };
+    (@ENUM $scope:tt $mode:ident [$($generics:tt)*] [$($seen:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::assert_has_field!(@ENUM $scope $mode [$($generics)*] [$($seen)* $token] $($rest)*);
+    };
+    (@ENUM $scope:tt $mode:ident [$($generics:tt)*] [$($seen:tt)*]) => {
+        $crate::assert_has_field!(@CHECK_VARIANT $scope $mode [$($generics)*] $($seen)*);
+    };
+    (
+        @CHECK_ALL $scope:tt $mode:ident [$($generics:tt)*]
+        [$($enum:tt)+],
+        {
+            $($items:tt)*
+        }
+        $(,)?
+    ) => {
+        $crate::assert_has_field!(@CHECK_ALL $scope $mode [$($generics)*] [$($enum)+], $($items)*);
+    };
+    (
+        @CHECK_ALL $scope:tt deref [$($generics:tt)*]
+        [$($enum:tt)+],
+        // The field names and the optional types are parsed by the `@ALL` arms
+        $($rest:tt)+
+    ) => {
+        $crate::assert_has_field!(@EMIT $scope [$($generics)*] () {
+            $crate::assert_has_field!(@ALL [$($enum)+]; $($rest)+);
+        });
+    };
+    (
+        @CHECK_VARIANT $scope:tt $mode:ident [$($generics:tt)*]
+        $variant:path,
+        {
+            $($items:tt)*
+        }
+        $(,)?
+    ) => {
+        $crate::assert_has_field!(@CHECK_VARIANT $scope $mode [$($generics)*] $variant, $($items)*);
+    };
+    (
+        @CHECK_VARIANT $scope:tt deref [$($generics:tt)*]
+        $variant:path,
+        // The field paths and the optional type assertions are parsed by the `@VARIANT` arms
+        $($rest:tt)+
+    ) => {
+        $crate::assert_has_field!(@EMIT $scope [$($generics)*] () {
+            $crate::assert_has_field!(@VARIANT $variant; $($rest)+);
+        });
+    };
+    (
+        @CHECK $scope:tt $mode:ident [$($generics:tt)*]
+        $struct:ty,
+        {
+            $($items:tt)*
+        }
+        $(,)?
+    ) => {
+        $crate::assert_has_field!(@CHECK $scope $mode [$($generics)*] $struct, $($items)*);
+    };
+    (
+        @CHECK $scope:tt $mode:ident [$($generics:tt)*]
+        $struct:ty,
+        // The field paths and the optional type assertions are parsed by the `@ASSERT` arms
+        $($rest:tt)+
+    ) => {
+        // The struct is accessed through a reference parameter because, unlike a value,
+        // it can point to an unsized struct, and, unlike a local variable, it provides
+        // the implied bounds of the struct, such as `T: 'a` for a field of type `&'a T`.
+        $crate::assert_has_field!(@EMIT $scope [$($generics)*] (struct_ref: &$struct) {
+            $crate::assert_has_field!(@ASSERT $mode struct_ref: $struct; $($rest)+);
+        });
+    };
+    (@EMIT [item] [$($generics:tt)*] ($($params:tt)*) { $($body:tt)* }) => {
+        // The const block forces the type-checking of the dummy function.
+        #[allow(
+            dead_code,
+            clippy::extra_unused_lifetimes,
+            clippy::extra_unused_type_parameters,
+        )]
+        const _: () = {
+            // We define a dummy function instead of performing the checks directly
+            // because the function calls in `:` syntax would be non-constant.
+            //
+            // At the moment of writing, a non-constant function call falsly compiled but oh well
+            //
+            // The generic parameters of the dummy function are the ones of the `for<...>` binder, if any,
+            // so the checks hold for all of their instantiations.
+            //
+            // Rust performs the type-checking of the function even though it is never called.
+            fn dummy<$($generics)*>($($params)*) {
+                $($body)*
+            }
+        };
+    };
+    (@EMIT [let] [] ($($params:tt)*) { $($body:tt)* }) => {
+        // Unlike a nested function, a closure can use the generic parameters and `Self`
+        // of the enclosing function or impl block. It is never called but type-checked anyway.
+        let _ = |$($params)*| {
+            $($body)*
+        };
+    };
+    (@EMIT [const $name:ident] [] ($($params:tt)*) { $($body:tt)* }) => {
+        // Unlike the items in the module scope, the associated constants must be named.
+        #[allow(dead_code)]
+        const $name: () = {
+            // Unlike a nested function, a closure can use the generic parameters and `Self`
+            // of the enclosing impl block. It is never called but type-checked anyway.
+            let _ = |$($params)*| {
+                $($body)*
+            };
+        };
+    };
+    (@MODE $scope:tt strict $($rest:tt)+) => {
+        $crate::assert_has_field!(@BINDER $scope strict $($rest)+);
+    };
+    (@MODE $scope:tt derived $($rest:tt)+) => {
+        $crate::assert_has_field!(@BINDER $scope derived $($rest)+);
+    };
+    (@MODE $scope:tt deref $($rest:tt)+) => {
+        $crate::assert_has_field!(@BINDER $scope deref $($rest)+);
+    };
+    (@MODE $scope:tt $($rest:tt)+) => {
+        // Following `Deref` is the default for backward compatibility
+        $crate::assert_has_field!(@BINDER $scope deref $($rest)+);
+    };
+    (@$rule:ident $($rest:tt)*) => {
+        // The malformed input that no internal rule accepts must not be parsed from scratch again,
+        // which would recurse until the recursion limit.
+        ::core::compile_error!("malformed `assert_has_field!` invocation, expected e.g. `assert_has_field!(Struct, field: Type)`");
+    };
+    (let $($rest:tt)+) => {
+        // The block makes the statement usable in expression position
+        {
+            $crate::assert_has_field!(@MODE [let] $($rest)+);
+        }
+    };
+    (const $name:ident for $($rest:tt)+) => {
+        $crate::assert_has_field!(@MODE [const $name] $($rest)+);
+    };
+    ($($rest:tt)+) => {
+        $crate::assert_has_field!(@MODE [item] $($rest)+);
+    };
+}
+
+/// This macro performs a compile-time check that a struct does *not* have a specific field.
+///
+/// Unlike [`assert_has_field!`], which relies on the field access expressions, the absence of a field
+/// can't be observed by the type checker directly. Therefore, the struct must derive
+/// [`HasField`](trait@HasField), which is available with the `derive` feature.
+///
+/// ## Syntax
+///
+/// 1. `assert_lacks_field!(Struct, field);` - checks that the struct has no field with the given name.
+/// 2. `assert_lacks_field!(Struct, field: Type);` - checks that the struct has no field with the given name and type.
+///
+/// ## Examples
+///
+/// ```rust
+/// use assert_has_field::{assert_lacks_field, HasField};
+///
+/// #[allow(dead_code)]
+/// #[derive(HasField)]
+/// struct ProfileDto {
+///     name: String,
+///     email: String,
+/// }
+///
+/// // This will compile because `ProfileDto` does not have a field `candidate_id`.
+/// assert_lacks_field!(ProfileDto, candidate_id);
+/// // This will compile because `ProfileDto`'s field `email` is not of type `u64`.
+/// assert_lacks_field!(ProfileDto, email: u64);
+/// ```
+///
+/// If the field is present, the macro will cause a compile-time error.
+///
+/// ```rust,compile_fail
+/// use assert_has_field::{assert_lacks_field, HasField};
+///
+/// #[allow(dead_code)]
+/// #[derive(HasField)]
+/// struct ProfileDto {
+///     name: String,
+///     candidate_id: u64,
+/// }
+///
+/// // This will cause a compile-time error because `ProfileDto` has a field `candidate_id`.
+/// assert_lacks_field!(ProfileDto, candidate_id);
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::{assert_lacks_field, HasField};
+///
+/// #[allow(dead_code)]
+/// #[derive(HasField)]
+/// struct ProfileDto {
+///     name: String,
+///     candidate_id: u64,
+/// }
+///
+/// // This will cause a compile-time error because `ProfileDto` has a field `candidate_id` of type `u64`.
+/// assert_lacks_field!(ProfileDto, candidate_id: u64);
+/// ```
+///
+/// The struct must derive [`HasField`](trait@HasField), otherwise the assertion would hold vacuously.
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_lacks_field;
+///
+/// #[allow(dead_code)]
+/// struct ProfileDto {
+///     name: String,
+/// }
+///
+/// // This will cause a compile-time error because `ProfileDto` does not derive `HasField`.
+/// assert_lacks_field!(ProfileDto, candidate_id);
+/// ```
+#[macro_export]
+macro_rules! assert_lacks_field {
+    (@ASSERT $struct:ty, $field:tt) => {
+        let _ = <$struct as $crate::secret::LacksField<
+            { $crate::secret::segment_id(::core::stringify!($field)) },
+            _,
+        >>::some_item;
+    };
+    (@ASSERT $struct:ty, $field:tt : $field_ty:ty) => {
+        let _ = <$struct as $crate::secret::LacksFieldOfType<
+            { $crate::secret::segment_id(::core::stringify!($field)) },
+            $field_ty,
+            _,
+        >>::some_item;
+    };
+    (
+        $struct:ty,
+        // Either a field name or a tuple index
+        $field:tt
+            $($rest:tt)*
+    ) => {
+        #[allow(dead_code)]
+        const _: () = {
+            fn dummy() {
+                $crate::secret::must_derive_has_field::<$struct>();
+                $crate::assert_lacks_field!(@ASSERT $struct, $field $($rest)*);
+            }
+        };
+    };
+}
+
+/// This macro performs a compile-time check that a struct has exactly the given fields,
+/// so that adding or removing a field breaks the compilation.
+///
+/// The fields are checked like with `assert_has_field!(strict Struct, { ... })`,
+/// and, additionally, a struct expression listing only the given fields is type-checked,
+/// which is rejected with an error naming every missing or unexpected field.
+///
+/// ## Syntax
+///
+/// `assert_fields_exact!(Struct, { field1: Type1, field2 });` - checks that the listed fields
+/// are all the fields of the struct and, for the fields with a type, that the field is of that type.
+/// Tuple structs list their indices, e.g. `assert_fields_exact!(Wrapper, { 0: u64 });`.
+///
+/// Since the struct expression can't be written for every type, the struct must be sized
+/// and its fields must be visible.
+///
+/// ## Examples
+///
+/// ```rust
+/// use assert_has_field::assert_fields_exact;
+///
+/// #[allow(dead_code)]
+/// struct Point {
+///     x: u64,
+///     y: u64,
+/// }
+///
+/// assert_fields_exact!(Point, { x: u64, y: u64 });
+/// assert_fields_exact!(Point, { y, x });
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_fields_exact;
+///
+/// #[allow(dead_code)]
+/// struct Point {
+///     x: u64,
+///     y: u64,
+///     z: u64,
+/// }
+///
+/// // This will cause a compile-time error because `Point` also has the field `z`.
+/// assert_fields_exact!(Point, { x: u64, y: u64 });
+/// ```
+#[macro_export]
+macro_rules! assert_fields_exact {
+    (
+        $struct:ty,
+        {
+            // Either field names or tuple indices
+            $($field:tt $(: $field_ty:ty)?),*
+            $(,)?
+        }
+        $(,)?
+    ) => {
+        $crate::assert_has_field!(strict $struct, { $($field $(: $field_ty)?),* });
+
+        #[allow(dead_code)]
+        const _: () = {
+            // Unlike a type, a type alias can be used as the name of a struct expression.
+            type Alias = $struct;
+
+            fn dummy() {
+                // Unlike a pattern without `..`, a struct expression names all the missing fields.
+                let _ = Alias {
+                    $($field: $crate::secret::unreachable_value(),)*
+                };
+            }
+        };
+    };
+}
+
+/// This macro performs a compile-time check that a type is covariant or contravariant in a lifetime.
+///
+/// A change of a field from `&'a str` to `Cell<&'a str>` silently makes the struct invariant in `'a`,
+/// which breaks the code that relies on shortening the lifetime. This macro turns such a change
+/// into a compile-time error.
+///
+/// ## Syntax
+///
+/// 1. `assert_variance!(Type<'a>, covariant in 'a);` - checks that `Type<'long>` is a subtype of `Type<'short>`.
+/// 2. `assert_variance!(Type<'a>, contravariant in 'a);` - checks that `Type<'short>` is a subtype of `Type<'long>`.
+///
+/// The lifetime is introduced by the macro, so the other generic arguments of the type must be concrete.
+/// The variance of individual fields is checked with `assert_has_field!(for<'a> Type<'a>, field: covariant in 'a);`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use assert_has_field::{assert_has_field, assert_variance};
+///
+/// #[allow(dead_code)]
+/// struct Ref<'a> {
+///     name: &'a str,
+/// }
+///
+/// #[allow(dead_code)]
+/// struct Callback<'a> {
+///     on_name: fn(&'a str),
+/// }
+///
+/// assert_variance!(Ref<'a>, covariant in 'a);
+/// assert_variance!(Callback<'a>, contravariant in 'a);
+/// assert_has_field!(for<'a> Ref<'a>, name: covariant in 'a);
+/// assert_has_field!(for<'a> Callback<'a>, on_name: contravariant in 'a);
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_variance;
+/// use core::cell::Cell;
+///
+/// #[allow(dead_code)]
+/// struct Ref<'a> {
+///     name: Cell<&'a str>,
+/// }
+///
+/// // This will cause a compile-time error because `Cell` makes `Ref` invariant in `'a`.
+/// assert_variance!(Ref<'a>, covariant in 'a);
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Callback<'a> {
+///     on_name: fn(&'a str),
+/// }
+///
+/// // This will cause a compile-time error because `fn(&'a str)` is contravariant in `'a`.
+/// assert_has_field!(for<'a> Callback<'a>, on_name: covariant in 'a);
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_has_field;
+///
+/// #[allow(dead_code)]
+/// struct Ref<'a> {
+///     name: &'a str,
+/// }
+///
+/// // This will cause a compile-time error because `&'a str` is covariant in `'a`.
+/// assert_has_field!(for<'a> Ref<'a>, name: contravariant in 'a);
+/// ```
+#[macro_export]
+macro_rules! assert_variance {
+    ($type:ty, covariant in $lt:lifetime $(,)?) => {
+        #[allow(dead_code)]
+        const _: () = {
+            // The type alias turns the lifetime into a parameter that can be instantiated twice.
+            type Alias<$lt> = $type;
+
+            // The raw pointers are covariant in their pointee, so the coercion is a subtyping check.
+            fn covariant<'short, 'long: 'short>(long: *const Alias<'long>) -> *const Alias<'short> {
+                long
+            }
+        };
+    };
+    ($type:ty, contravariant in $lt:lifetime $(,)?) => {
+        #[allow(dead_code)]
+        const _: () = {
+            // The type alias turns the lifetime into a parameter that can be instantiated twice.
+            type Alias<$lt> = $type;
+
+            // The raw pointers are covariant in their pointee, so the coercion is a subtyping check.
+            fn contravariant<'short, 'long: 'short>(
+                short: *const Alias<'short>,
+            ) -> *const Alias<'long> {
+                short
+            }
+        };
+    };
+}
+
+/// This macro performs a compile-time check that an enum has a specific variant.
+///
+/// ## Syntax
+///
+/// 1. `assert_has_variant!(Enum, Variant);` - checks that the enum has a unit variant with the given name.
+/// 2. `assert_has_variant!(Enum, Variant(Type1, Type2));` - checks that the enum has a tuple-like variant
+///    with exactly the fields of the given types.
+/// 3. `assert_has_variant!(Enum, Variant { field1: Type1, field2: Type2 });` - checks that the enum has
+///    a struct-like variant with exactly the given fields of the given types. Ending the fields with `..`
+///    allows the variant to have other fields as well.
+///
+/// ## Examples
+///
+/// ```rust
+/// use assert_has_field::assert_has_variant;
+///
+/// #[allow(dead_code)]
+/// enum Status {
+///     Active,
+///     Failed(&'static str),
+///     Pending { since: u64, attempts: u8 },
+/// }
+///
+/// assert_has_variant!(Status, Active);
+/// assert_has_variant!(Status, Failed(&'static str));
+/// assert_has_variant!(Status, Pending { since: u64, attempts: u8 });
+/// assert_has_variant!(Status, Pending { since: u64, .. });
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_has_variant;
+///
+/// #[allow(dead_code)]
+/// enum Status {
+///     Active,
+///     Pending { since: u64, attempts: u8 },
+/// }
+///
+/// // This will cause a compile-time error because `Status::Pending` also has the field `attempts`.
+/// assert_has_variant!(Status, Pending { since: u64 });
+/// ```
+#[macro_export]
+macro_rules! assert_has_variant {
+    (@FIELDS $variant:ident [$($seen:ident)*]) => {
+        // Unless the fields end with `..`, the struct expression is rejected if the variant has other fields.
+        let _ = Alias::$variant {
+            $($seen: $crate::secret::unreachable_value(),)*
+        };
+    };
+    (@FIELDS $variant:ident [$($seen:ident)*] ..) => {};
+    (@FIELDS $variant:ident [$($seen:ident)*] $field:ident : $field_ty:ty $(, $($rest:tt)*)?) => {
+        match $crate::secret::unreachable_value() {
+            Alias::$variant { $field: ref variant_field, .. } => {
+                $crate::secret::ty_must_eq::<_, $field_ty>(variant_field);
+            }
+            #[allow(unreachable_patterns)]
+            _ => {}
+        }
+        $crate::assert_has_variant!(@FIELDS $variant [$($seen)* $field] $($($rest)*)?);
+    };
+    ($enum:ty, $variant:ident $($fields:tt)?) => {
+        #[allow(dead_code)]
+        const _: () = {
+            // Unlike a type, a type alias can be followed by a variant name in paths and patterns.
+            type Alias = $enum;
+
+            fn dummy() {
+                $crate::assert_has_variant!(@VARIANT $variant $($fields)?);
+            }
+        };
+    };
+    (@VARIANT $variant:ident) => {
+        // A path pattern only matches a unit variant.
+        match $crate::secret::unreachable_value() {
+            Alias::$variant => {}
+            #[allow(unreachable_patterns)]
+            _ => {}
+        }
+    };
+    (@VARIANT $variant:ident ($($field_ty:ty),* $(,)?)) => {
+        // The constructor of a tuple-like variant is a function of its fields.
+        let _: fn($($field_ty),*) -> Alias = Alias::$variant;
+    };
+    (@VARIANT $variant:ident { $($fields:tt)* }) => {
+        $crate::assert_has_variant!(@FIELDS $variant [] $($fields)*);
+    };
+}
+
+/// This macro performs a compile-time check that an enum has exactly the given variants,
+/// so that adding or removing a variant breaks the compilation.
+///
+/// ## Syntax
+///
+/// `assert_variants!(Enum, { Variant1, Variant2(..), Variant3 { .. } });` - checks that the listed
+/// variants are all the variants of the enum. Every variant is written as a pattern, so `Variant(_)`
+/// also checks the number of fields of a tuple-like variant.
+///
+/// ## Examples
+///
+/// ```rust
+/// use assert_has_field::assert_variants;
+///
+/// #[allow(dead_code)]
+/// enum Status {
+///     Active,
+///     Failed(&'static str),
+///     Pending { since: u64 },
+/// }
+///
+/// assert_variants!(Status, { Active, Failed(_), Pending { .. } });
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::assert_variants;
+///
+/// #[allow(dead_code)]
+/// enum Status {
+///     Active,
+///     Failed(&'static str),
+///     Pending { since: u64 },
+///     Cancelled,
+/// }
+///
+/// // This will cause a compile-time error because `Status::Cancelled` is not listed.
+/// assert_variants!(Status, { Active, Failed(_), Pending { .. } });
+/// ```
+#[macro_export]
+macro_rules! assert_variants {
+    (
+        $enum:ty,
+        {
+            $($variant:ident $(($($tuple:tt)*))? $({ $($named:tt)* })?),*
+            $(,)?
+        }
+        $(,)?
+    ) => {
+        #[allow(dead_code, unreachable_code)]
+        const _: () = {
+            // Unlike a type, a type alias can be followed by a variant name in patterns.
+            type Alias = $enum;
+
+            fn dummy() {
+                // The match has no wildcard, so it is rejected unless the variants are exhaustive.
+                match $crate::secret::unreachable_value::<Alias>() {
+                    $(Alias::$variant $(($($tuple)*))? $({ $($named)* })? => {})*
+                }
+            }
+        };
+    };
+}
+
+/// This macro evaluates to the name of a field as a `&'static str`, e.g. `"profile.candidate_id"`,
+/// after checking that the field exists like [`assert_has_field!`] does.
+///
+/// The names stay in sync with the struct, so they are suitable for the keys of logs,
+/// the column names of queries, and the error messages.
+///
+/// ## Syntax
+///
+/// 1. `field_name!(Struct, field)` - the name of a field of the struct, which can be a dotted path,
+///    e.g. `field_name!(Struct, a.b.0)`.
+/// 2. `field_name!(strict Struct, field)` - the same, but the field must be declared on the struct itself.
+/// 3. `field_name!(enum Enum::Variant, field)` - the name of a field of the enum variant.
+///
+/// The macro expands to a constant expression, so it can be used to initialize constants and statics.
+///
+/// ## Examples
+///
+/// ```rust
+/// use assert_has_field::field_name;
+///
+/// #[allow(dead_code)]
+/// struct Ids {
+///     candidate_id: u64,
+/// }
+///
+/// #[allow(dead_code)]
+/// struct Profile {
+///     ids: Ids,
+/// }
+///
+/// #[allow(dead_code)]
+/// struct Candidate {
+///     profile: Profile,
+/// }
+///
+/// const CANDIDATE_ID: &str = field_name!(Candidate, profile.ids.candidate_id);
+/// assert_eq!(CANDIDATE_ID, "profile.ids.candidate_id");
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::field_name;
+///
+/// #[allow(dead_code)]
+/// struct Candidate {
+///     id: u64,
+/// }
+///
+/// // This will cause a compile-time error because `Candidate` does not have a field `candidate_id`.
+/// const CANDIDATE_ID: &str = field_name!(Candidate, candidate_id);
+/// ```
+#[macro_export]
+macro_rules! field_name {
+    (@NAME $head:tt $(. $tail:tt)*) => {
+        ::core::concat!(::core::stringify!($head) $(, ".", ::core::stringify!($tail))*)
+    };
+    (strict $struct:ty, $($field:tt).+ $(,)?) => {{
+        $crate::assert_has_field!(strict $struct, $($field).+);
+        $crate::field_name!(@NAME $($field).+)
+    }};
+    (deref $struct:ty, $($field:tt).+ $(,)?) => {{
+        $crate::assert_has_field!(deref $struct, $($field).+);
+        $crate::field_name!(@NAME $($field).+)
+    }};
+    (enum $variant:path, $($field:tt).+ $(,)?) => {{
+        $crate::assert_has_field!(enum $variant, $($field).+);
+        $crate::field_name!(@NAME $($field).+)
+    }};
+    ($struct:ty, $($field:tt).+ $(,)?) => {{
+        $crate::assert_has_field!($struct, $($field).+);
+        $crate::field_name!(@NAME $($field).+)
+    }};
+}
+
+/// This macro names the type of a field in type position, e.g. `Vec<field_type!(Order, id)>`,
+/// so that the dependent code follows the field when its type changes.
+///
+/// The type is found through the [`HasField`](trait@HasField) implementations, so every struct
+/// on the path must derive [`HasField`](trait@HasField), which is available with the `derive` feature.
+/// The private fields of a `pub` struct don't implement `HasField`, so their types can't be named.
+///
+/// ## Syntax
+///
+/// `field_type!(Struct, field)` - the type of a field of the struct, which can be a dotted path,
+/// e.g. `field_type!(Struct, a.b)`. Since tuples don't derive `HasField`, the indices can only
+/// be used for the fields of tuple structs. A nested index must be separated from the preceding
+/// index with a space, e.g. `field_type!(Struct, 1 .0)`, because `1.0` is lexed as a float literal.
+///
+/// The struct can be a generic parameter bounded by `HasField`, e.g. `field_type!(T, id)`
+/// for `T: HasField<{ field_id("id") }>`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use assert_has_field::{field_type, HasField};
+///
+/// #[allow(dead_code)]
+/// #[derive(HasField)]
+/// struct Profile {
+///     candidate_id: u64,
+/// }
+///
+/// #[allow(dead_code)]
+/// #[derive(HasField)]
+/// struct Candidate {
+///     profile: Profile,
+/// }
+///
+/// let ids: Vec<field_type!(Candidate, profile.candidate_id)> = vec![1, 2, 3];
+/// # let _: Vec<u64> = ids;
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::{field_type, HasField};
+///
+/// #[allow(dead_code)]
+/// #[derive(HasField)]
+/// struct Candidate {
+///     id: u64,
+/// }
+///
+/// // This will cause a compile-time error because `Candidate` does not have a field `candidate_id`.
+/// let id: field_type!(Candidate, candidate_id) = 1;
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::{field_type, HasField};
+///
+/// #[allow(dead_code)]
+/// #[derive(HasField)]
+/// struct Pair(u8, u16);
+///
+/// #[allow(dead_code)]
+/// #[derive(HasField)]
+/// struct Pairs(u8, Pair);
+///
+/// // This will cause a compile-time error because `1.1` is lexed as a float literal, unlike `1 .1`.
+/// let second: field_type!(Pairs, 1.1) = 1;
+/// ```
+#[macro_export]
+macro_rules! field_type {
+    ($struct:ty, $field:tt $(,)?) => {
+        <$struct as $crate::HasField<{ $crate::secret::segment_id(::core::stringify!($field)) }>>::Type
+    };
+    ($struct:ty, $field:tt . $($rest:tt).+ $(,)?) => {
+        $crate::field_type!(
+            <$struct as $crate::HasField<{ $crate::secret::segment_id(::core::stringify!($field)) }>>::Type,
+            $($rest).+
+        )
+    };
+}
+
+/// This macro evaluates to a function pointer that returns a reference to a field,
+/// e.g. `fn(&Candidate) -> &u64` for `field_getter!(Candidate, profile.candidate_id)`.
+///
+/// The path is checked like with [`assert_has_field!`], but the result is a value that can be
+/// passed to sorting, grouping, and lens-style utilities, so the key extraction can't drift
+/// from the definition of the struct.
+///
+/// ## Syntax
+///
+/// 1. `field_getter!(Struct, field)` - a `fn(&Struct) -> &Field` for a field, which can be a dotted path.
+/// 2. `field_getter!(mut Struct, field)` - a `fn(&mut Struct) -> &mut Field`.
+///
+/// Just like with [`assert_has_field!`], the fields are found through [`Deref`](core::ops::Deref) by default,
+/// and prefixing the struct with `strict`, e.g. `field_getter!(mut strict Struct, field)`, requires
+/// the fields to be declared on the structs themselves. Since the getter returns a reference,
+/// it can't be created for the fields of `#[repr(packed)]` structs.
+///
+/// ## Examples
+///
+/// ```rust
+/// use assert_has_field::field_getter;
+///
+/// #[allow(dead_code)]
+/// struct Profile {
+///     candidate_id: u64,
+/// }
+///
+/// #[allow(dead_code)]
+/// struct Candidate {
+///     name: &'static str,
+///     profile: Profile,
+/// }
+///
+/// let mut candidates = vec![
+///     Candidate { name: "b", profile: Profile { candidate_id: 2 } },
+///     Candidate { name: "a", profile: Profile { candidate_id: 1 } },
+/// ];
+///
+/// let candidate_id = field_getter!(Candidate, profile.candidate_id);
+/// candidates.sort_by_key(|candidate| *candidate_id(candidate));
+/// assert_eq!(candidates[0].name, "a");
+///
+/// let name = field_getter!(mut Candidate, name);
+/// *name(&mut candidates[0]) = "c";
+/// assert_eq!(candidates[0].name, "c");
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::field_getter;
+///
+/// #[allow(dead_code)]
+/// struct Candidate {
+///     id: u64,
+/// }
+///
+/// // This will cause a compile-time error because `Candidate` does not have a field `candidate_id`.
+/// let candidate_id = field_getter!(Candidate, candidate_id);
+/// ```
+#[macro_export]
+macro_rules! field_getter {
+    (@GETTER [$($mut:tt)?] strict $struct:ty, $($field:tt).+ $(,)?) => {{
+        $crate::assert_has_field!(let strict $struct, $($field).+);
+        $crate::field_getter!(@GETTER [$($mut)?] deref $struct, $($field).+)
+    }};
+    (@GETTER [$($mut:tt)?] deref $struct:ty, $($field:tt).+ $(,)?) => {{
+        // The closure doesn't capture anything, so it coerces to a function pointer,
+        // whose elided lifetimes tie the reference to the field to the reference to the struct.
+        let getter: fn(&$($mut)? $struct) -> &$($mut)? _ = |value| &$($mut)? value $(.$field)+;
+        getter
+    }};
+    (@GETTER [$($mut:tt)?] $struct:ty, $($field:tt).+ $(,)?) => {
+        $crate::field_getter!(@GETTER [$($mut)?] deref $struct, $($field).+)
+    };
+    (@$rule:ident $($rest:tt)*) => {
+        // The malformed input must not be parsed from scratch again, which would recurse
+        // until the recursion limit.
+        ::core::compile_error!("malformed `field_getter!` invocation, expected e.g. `field_getter!(Struct, field)`")
+    };
+    (mut $($rest:tt)+) => {
+        $crate::field_getter!(@GETTER [mut] $($rest)+)
+    };
+    ($($rest:tt)+) => {
+        $crate::field_getter!(@GETTER [] $($rest)+)
+    };
+}
+
+/// This macro declares a named set of fields, a "shape", that many structs can be checked against
+/// with [`assert_shape`].
+///
+/// ## Syntax
+///
+/// `field_shape!(Shape { field1: Type1, field2 :~ Type2, field3 });` - declares a shape with the given
+/// fields, which are written just like the braced fields of [`assert_has_field!`].
+///
+/// The shape is a `macro_rules!` macro named after the shape, so it has to be declared before
+/// it is used and can be shared across modules like any other `macro_rules!` macro,
+/// e.g. with `pub(crate) use Shape;`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use assert_has_field::{assert_shape, field_shape};
+///
+/// field_shape!(Audited {
+///     created_at: u64,
+///     updated_at: u64,
+///     created_by :~ &str,
+/// });
+///
+/// #[allow(dead_code)]
+/// struct OrderDto {
+///     id: u64,
+///     created_at: u64,
+///     updated_at: u64,
+///     created_by: &'static str,
+/// }
+///
+/// assert_shape!(OrderDto: Audited);
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::{assert_shape, field_shape};
+///
+/// field_shape!(Audited {
+///     created_at: u64,
+///     updated_at: u64,
+/// });
+///
+/// #[allow(dead_code)]
+/// struct OrderDto {
+///     id: u64,
+///     created_at: u64,
+/// }
+///
+/// // This will cause a compile-time error because `OrderDto` does not have a field `updated_at`.
+/// assert_shape!(OrderDto: Audited);
+/// ```
+#[macro_export]
+macro_rules! field_shape {
+    // `$d` is the `$` token, which can't be written directly in the generated macro.
+    (@DEFINE ($d:tt) $shape:ident { $($items:tt)* }) => {
+        #[allow(unused_macros)]
+        macro_rules! $shape {
+            ($d($d subject:tt)+) => {
+                $crate::assert_has_field! { $d($d subject)+, { $($items)* } }
+            };
+        }
+    };
+    ($shape:ident { $($items:tt)* } $(;)?) => {
+        $crate::field_shape!(@DEFINE ($) $shape { $($items)* });
+    };
+}
+
+/// This macro performs a compile-time check that a struct has all the fields of one or more shapes
+/// declared with [`field_shape`].
+///
+/// ## Syntax
+///
+/// `assert_shape!(Struct: Shape1 + Shape2);` - checks that the struct has the fields of every shape.
+/// Every missing or mistyped field is reported separately, and the error of a mistyped field points
+/// at its type in the declaration of the shape. The shapes can also be named by their paths,
+/// e.g. `assert_shape!(Struct: shapes::Audited);`.
+///
+/// The struct can be prefixed with `strict`, `deref`, or `derived`, which have the same meaning as
+/// for [`assert_has_field!`]. Prefixing the assertion with `let`, e.g. `assert_shape!(let Self: Shape);`,
+/// expands it to a statement instead of an item.
+///
+/// ## Examples
+///
+/// ```rust
+/// use assert_has_field::{assert_shape, field_shape};
+///
+/// field_shape!(Identified { id: u64 });
+/// field_shape!(Named { name: &'static str });
+///
+/// #[allow(dead_code)]
+/// struct User {
+///     id: u64,
+///     name: &'static str,
+/// }
+///
+/// assert_shape!(strict User: Identified + Named);
+/// ```
+///
+/// ```rust,compile_fail
+/// use assert_has_field::{assert_shape, field_shape};
+///
+/// field_shape!(Audited {
+///     created_at: u64,
+///     updated_at: u64,
+/// });
+///
+/// #[allow(dead_code)]
+/// struct OrderDto {
+///     created_at: u64,
+///     updated_at: u32,
+/// }
+///
+/// // This will cause a compile-time error because the field `updated_at` of `OrderDto` is of type `u32`.
+/// assert_shape!(OrderDto: Audited);
+/// ```
+#[macro_export]
+macro_rules! assert_shape {
+    // A `path` fragment can't be followed by `+`, so the paths of the shapes are matched segment by segment.
+    (@SHAPES $prefix:tt $struct:ty : $($first:ident)::+ $(+ $($shape:ident)::+)* $(;)?) => {
+        $crate::assert_shape!(@SHAPE $prefix $struct, $($first)::+);
+        $($crate::assert_shape!(@SHAPE $prefix $struct, $($shape)::+);)*
+    };
+    (@SHAPE [$($prefix:tt)*] $struct:ty, $shape:path) => {
+        $shape! { $($prefix)* $struct }
+    };
+    (let $($rest:tt)+) => {
+        {
+            $crate::assert_shape!(@LET [let] $($rest)+);
+        }
+    };
+    (@LET [$($prefix:tt)*] strict $($rest:tt)+) => {
+        $crate::assert_shape!(@SHAPES [$($prefix)* strict] $($rest)+);
+    };
+    (@LET [$($prefix:tt)*] deref $($rest:tt)+) => {
+        $crate::assert_shape!(@SHAPES [$($prefix)* deref] $($rest)+);
+    };
+    (@LET [$($prefix:tt)*] derived $($rest:tt)+) => {
+        $crate::assert_shape!(@SHAPES [$($prefix)* derived] $($rest)+);
+    };
+    (@LET [$($prefix:tt)*] $($rest:tt)+) => {
+        $crate::assert_shape!(@SHAPES [$($prefix)*] $($rest)+);
+    };
+    (@$rule:ident $($rest:tt)*) => {
+        // The malformed input must not be parsed from scratch again, which would recurse
+        // until the recursion limit.
+        ::core::compile_error!("malformed `assert_shape!` invocation, expected e.g. `assert_shape!(Struct: Shape1 + Shape2)`");
+    };
+    ($($rest:tt)+) => {
+        $crate::assert_shape!(@LET [] $($rest)+);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code)]
+    struct Point {
+        x: u64,
+        y: u64,
+    }
+
+    assert_has_field!(Point, x);
+    assert_has_field!(Point, x : u64);
+
+    struct Wrapper<T>(T);
+
+    impl<T> core::ops::Deref for Wrapper<T> {
+        type Target = T;
+
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    #[allow(dead_code)]
+    struct Point2 {
+        x: &'static Wrapper<u64>,
+        y: u64,
+    }
+
+    assert_has_field!(Point2, x :~ &'static u64);
+
+    assert_has_field!(Wrapper<u64>, 0);
+    assert_has_field!(Wrapper<u64>, 0: u64);
+    assert_has_field!(Wrapper<&'static Wrapper<u64>>, 0 :~ &'static u64);
+
+    assert_has_field!((u8, Wrapper<u64>), 0: u8);
+    assert_has_field!((u8, Wrapper<u64>), 1);
+
+    #[allow(dead_code)]
+    struct Line {
+        start: Point,
+        end: Point,
+        weights: ((u8, u16), u32),
+    }
+
+    assert_has_field!(Line, start.x);
+    assert_has_field!(Line, end.y: u64);
+    assert_has_field!(Line, weights.0.1: u16);
+    assert_has_field!(Line, weights.0 :~ (u8, u16));
+    assert_has_field!((Line, Wrapper<Point>), 1.0.x: u64);
+
+    assert_has_field!(Line, {
+        start,
+        start.x: u64,
+        end: Point,
+        end.y,
+        weights.0 :~ (u8, u16),
+        weights.0.1: u16,
+    });
+    assert_has_field!(Point2, { x :~ &'static u64, y });
+
+    assert_has_field!(deref Wrapper<Point>, x: u64);
+    assert_has_field!(strict Wrapper<Point>, 0.x: u64);
+    assert_has_field!(strict Line, { start.x, end: Point, weights.0.1: u16 });
+    assert_has_field!(strict (u8, Point2), 1.x :~ &'static u64);
+
+    extern crate alloc;
+
+    use alloc::{boxed::Box, string::String, vec::Vec};
+
+    #[allow(dead_code)]
+    struct Connection {
+        address: String,
+        buffer: Vec<u8>,
+        peer: (u64, String),
+    }
+
+    impl Drop for Connection {
+        fn drop(&mut self) {}
+    }
+
+    assert_has_field!(Connection, address: String);
+    assert_has_field!(Connection, buffer: Vec<u8>);
+    assert_has_field!(Connection, buffer :~ Vec<u8>);
+    assert_has_field!(Connection, {
+        address,
+        address: String,
+        address :~ String,
+        buffer: Vec<u8>,
+        peer: (u64, String),
+        peer.1: String,
+        peer.1 :~ String,
+    });
+    assert_has_field!(strict Connection, { address: String, buffer :~ Vec<u8> });
+
+    #[allow(dead_code)]
+    #[repr(C, packed)]
+    struct Frame {
+        kind: u8,
+        len: u32,
+        header: (u16, u64),
+        payload: Vec<u8>,
+        trailer: (u8, String),
+    }
+
+    assert_has_field!(Frame, len);
+    assert_has_field!(Frame, len: u32);
+    assert_has_field!(Frame, len :~ u32);
+    assert_has_field!(Frame, payload);
+    assert_has_field!(Frame, payload: Vec<u8>);
+    assert_has_field!(Frame, payload :~ Vec<u8>);
+    assert_has_field!(Frame, {
+        kind: u8,
+        header.1: u64,
+        header.1 :~ u64,
+        trailer.1,
+        trailer.1: String,
+        trailer.1 :~ String,
+    });
+    assert_has_field!(strict Frame, { len: u32, payload: Vec<u8>, trailer.1 :~ String });
+
+    #[allow(dead_code)]
+    #[repr(C, packed(2))]
+    struct Packed2 {
+        flag: bool,
+        items: Vec<u64>,
+    }
+
+    assert_has_field!(Packed2, { flag: bool, flag :~ bool, items: Vec<u64>, items :~ Vec<u64> });
+
+    #[allow(dead_code)]
+    struct Packet {
+        len: u16,
+        data: [u8],
+    }
+
+    #[allow(dead_code)]
+    struct Named<T: ?Sized> {
+        id: u32,
+        value: T,
+    }
+
+    assert_has_field!(Packet, data);
+    assert_has_field!(Packet, data: [u8]);
+    assert_has_field!(Packet, { len: u16, len :~ u16, data });
+    assert_has_field!(strict Packet, len: u16);
+    assert_has_field!(Named<str>, value: str);
+    assert_has_field!(Named<dyn core::fmt::Debug>, { id: u32, value: dyn core::fmt::Debug });
+    assert_has_field!(Named<Named<[u64]>>, { value.id: u32, value.value: [u64] });
+    assert_has_field!(Box<Named<[u64]>>, value: [u64]);
+
+    #[allow(dead_code)]
+    struct Generic<'a, T: ?Sized, const N: usize> {
+        name: &'a str,
+        items: [u64; N],
+        value: T,
+    }
+
+    assert_has_field!(for<T> Wrapper<T>, 0: T);
+    assert_has_field!(for<T: Clone> Wrapper<T>, 0 :~ T);
+    assert_has_field!(for<'a, T: ?Sized, const N: usize> Generic<'a, T, N>, {
+        name: &'a str,
+        name :~ &'a str,
+        items: [u64; N],
+        value: T,
+    });
+    assert_has_field!(for<'a> Generic<'a, str, 2>, name: &'a str);
+    assert_has_field!(for<T: Into<Wrapper<Wrapper<u8>>>> Wrapper<Wrapper<T>>, 0.0: T);
+    assert_has_field!(for<T: Iterator<Item = Wrapper<u8>>> (T, Point), 1.x: u64);
+    assert_has_field!(strict for<T> Wrapper<Named<T>>, 0.id: u32);
+    assert_has_field!(deref for<T> Box<Wrapper<T>>, 0: T);
+
+    impl<T> Wrapper<T> {
+        assert_has_field!(const ASSERT_INNER for Self, { 0, 0: T });
+        assert_has_field!(const ASSERT_INNER_STRICT for strict Self, 0 :~ T);
+
+        #[allow(dead_code)]
+        fn inner(&self) -> &T {
+            assert_has_field!(let Self, 0: T);
+            assert_has_field!(let strict Wrapper<T>, { 0, 0: T });
+            &self.0
+        }
+    }
+
+    trait Entity {
+        type Row;
+    }
+
+    #[allow(dead_code)]
+    const fn check_row<E: Entity<Row = Wrapper<Point>>>() {
+        assert_has_field!(let deref E::Row, { 0.x: u64, y: u64 });
+        assert_has_field!(let E::Row, 0)
+    }
+
+    #[allow(dead_code)]
+    enum Event<Id> {
+        Created {
+            id: Id,
+            at: Wrapper<u64>,
+            tags: Vec<&'static str>,
+        },
+        Deleted(Id),
+        Renamed(Id, (String, String)),
+    }
+
+    impl<Id> Drop for Event<Id> {
+        fn drop(&mut self) {}
+    }
+
+    assert_has_field!(enum Event<u8>::Created, id);
+    assert_has_field!(enum Event<u8>::Created, id: u8);
+    assert_has_field!(enum Event<u8>::Created, id :~ u8);
+    assert_has_field!(enum Event::<u8>::Deleted, 0: u8);
+    assert_has_field!(enum Event<u8>::Created, {
+        at.0: u64,
+        at.0 :~ u64,
+        tags: Vec<&'static str>,
+        tags :~ Vec<&'static str>,
+    });
+    assert_has_field!(for<Id> enum Event::<Id>::Renamed, { 0: Id, 1 .1: String, 1 .0 :~ String });
+    assert_has_field!(deref for<Id: Clone> enum Event<Id>::Deleted, 0 :~ Id);
+
+    impl<Id> Event<Id> {
+        assert_has_field!(const ASSERT_CREATED for enum Self::Created, id: Id);
+
+        #[allow(dead_code)]
+        fn id(&self) -> &Id {
+            assert_has_field!(let enum Self::Deleted, 0: Id);
+            match self {
+                Self::Created { id, .. } | Self::Deleted(id) | Self::Renamed(id, _) => id,
+            }
+        }
+    }
+
+    #[allow(dead_code)]
+    #[derive(crate::HasField)]
+    enum Command<T> {
+        Create { request_id: u64, payload: T },
+        Delete { request_id: u64 },
+        Retry { request_id: u64, attempts: T },
+    }
+
+    #[allow(dead_code)]
+    #[derive(crate::HasField)]
+    enum Tagged {
+        First(u8, &'static str),
+        Second(u8),
+    }
+
+    #[allow(dead_code)]
+    #[derive(crate::HasField)]
+    enum Never {}
+
+    assert_has_field!(enum Command::<u8>::*, request_id);
+    assert_has_field!(for<T> enum Command<T>::Create, { request_id: u64, payload: T });
+    assert_has_field!(enum Tagged::*, 0: u8);
+    assert_has_field!(enum Tagged::*, { 0, 0: u8 });
+    assert_has_field!(enum Never::*, anything: String);
+
+    #[allow(dead_code)]
+    #[derive(crate::HasField)]
+    enum List<T> {
+        Cons { value: T, next: Option<Box<Self>> },
+        Nil { next: Option<Box<Self>> },
+    }
+
+    assert_has_field!(enum List::<u8>::*, next: Option<Box<List<u8>>>);
+    assert_has_field!(for<T> enum List<T>::Cons, next: Option<Box<List<T>>>);
+
+    impl Tagged {
+        assert_has_field!(const ASSERT_TAG for enum Self::*, 0: u8);
+
+        #[allow(dead_code)]
+        fn tag(&self) -> u8 {
+            assert_has_field!(let enum Self::*, 0: u8);
+            match self {
+                Self::First(tag, _) | Self::Second(tag) => *tag,
+            }
+        }
+    }
+
+    #[allow(dead_code)]
+    enum Single {
+        Only { value: u8 },
+    }
+
+    assert_has_field!(enum Single::Only, value: u8);
+
+    #[allow(dead_code)]
+    enum Status<E> {
+        Active,
+        Failed(E, &'static str),
+        Pending { since: u64, attempts: Vec<E> },
+    }
+
+    impl<E> Drop for Status<E> {
+        fn drop(&mut self) {}
+    }
+
+    assert_has_field!(Point, x: impl Copy + Into<u128> + core::fmt::Debug);
+    assert_has_field!(Packet, data: impl core::fmt::Debug);
+    assert_has_field!(Frame, { payload: impl IntoIterator<Item = u8>, len: impl PartialOrd<u32> + Copy });
+    assert_has_field!(strict Connection, { peer.1: impl AsRef<str>, buffer: impl Extend<u8> });
+    assert_has_field!(for<'a, T: ?Sized, const N: usize> Generic<'a, T, N>, { items: impl Copy, name: impl Copy });
+    assert_has_field!(enum Event<u8>::Created, { id: impl Copy, tags: impl Clone + IntoIterator<Item = &'static str> });
+    assert_has_field!(Wrapper<fn(&str) -> usize>, 0: impl for<'a> Fn(&'a str) -> usize);
+
+    assert_has_field!(Connection, { peer: (u64, _), peer: (_, String), buffer :~ Vec<_> });
+    assert_has_field!(Frame, { header: (_, _), payload: Vec<_> });
+    assert_has_field!(Packet, data: [_]);
+    assert_has_field!(for<T> Wrapper<Vec<T>>, 0: Vec<_>);
+    assert_has_field!(enum Event<u8>::Created, { tags: Vec<_>, at: Wrapper<_> });
+    assert_has_field!(enum Command<u8>::*, request_id: _);
+
+    impl Connection {
+        assert_has_field!(const ASSERT_PEER for Self, peer: (_, String));
+    }
+
+    assert_has_field!(Point, { x: !u32, y: !i64, x: !&'static u64 });
+    assert_has_field!(Connection, { address: !&'static str, peer.1: !Vec<u8>, buffer: !Vec<u16> });
+    assert_has_field!(strict Frame, payload: !String);
+    assert_has_field!(Packet, data: ![u16]);
+    assert_has_field!(for<T> Wrapper<T>, 0: !Wrapper<T>);
+    assert_has_field!(enum Event<u8>::Created, { id: !u16, tags: !Vec<String> });
+
+    assert_has_field!(Point, { x :> u128, x :?> u8, y :?> i64 });
+    assert_has_field!(strict Connection, { address :> Box<str>, peer.1 :> Vec<u8>, peer :?> (u64, String) });
+    assert_has_field!(Frame, { kind :> u16, len :?> u16 });
+    assert_has_field!(for<T: Into<u64>> Wrapper<T>, 0 :> u64);
+    assert_has_field!(enum Event<u8>::Created, { id :> u32, id :?> i8, tags :> Box<[&'static str]> });
+
+    #[allow(dead_code)]
+    struct Borrowed<'a, T: ?Sized> {
+        name: &'a str,
+        names: Vec<&'a str>,
+        callback: fn(&'a str) -> usize,
+        cell: core::cell::Cell<&'a str>,
+        value: &'a T,
+    }
+
+    assert_variance!(Generic<'a, [u8], 4>, covariant in 'a);
+    assert_variance!(Wrapper<fn(&'a str) -> usize>, contravariant in 'a);
+    assert_variance!(fn(&'a str), contravariant in 'a);
+    assert_variance!(Packet, covariant in 'a);
+    assert_has_field!(for<'a> Borrowed<'a, [u8]>, {
+        name: covariant in 'a,
+        names: covariant in 'a,
+        callback: contravariant in 'a,
+        value: covariant in 'a,
+        cell,
+    });
+    assert_has_field!(for<'a> Generic<'a, str, 4>, name: covariant in 'a);
+
+    assert_fields_exact!(Point, { x: u64, y: u64 });
+    assert_fields_exact!(Point, { y, x: u64, });
+    assert_fields_exact!(Wrapper<u8>, { 0: u8 });
+    assert_fields_exact!(Connection, { address: String, buffer: Vec<u8>, peer: (u64, String) });
+    assert_fields_exact!(Frame, { kind: u8, len: u32, header, payload: Vec<u8>, trailer: (u8, String) });
+    assert_fields_exact!(Packed2, { items, flag });
+
+    assert_has_variant!(Status<u8>, Active);
+    assert_has_variant!(Status<u8>, Failed(u8, &'static str));
+    assert_has_variant!(Status<u8>, Pending { since: u64, attempts: Vec<u8> });
+    assert_has_variant!(Status<u8>, Pending { attempts: Vec<u8>, since: u64, });
+    assert_has_variant!(Status<u8>, Pending { since: u64, .. });
+    assert_variants!(Status<u8>, { Active, Failed(_, _), Pending { .. } });
+    assert_variants!(Status<u8>, { Pending { since: _, .. }, Failed(..), Active, });
+    assert_variants!(Never, {});
+
+    #[allow(dead_code)]
+    #[derive(crate::HasField)]
+    struct Profile<T> {
+        pub name: &'static str,
+        pub id: T,
+    }
+
+    #[allow(dead_code)]
+    #[derive(crate::HasField)]
+    struct Pair(pub u8, pub Profile<u64>);
+
+    assert_lacks_field!(Profile<u64>, candidate_id);
+    assert_lacks_field!(Profile<u64>, id: u32);
+    assert_lacks_field!(Profile<u64>, name: &'static [u8]);
+    assert_lacks_field!(Pair, 2);
+    assert_lacks_field!(Pair, 1: Profile<u32>);
+
+    #[allow(dead_code)]
+    #[derive(crate::HasField)]
+    #[repr(C, packed(2))]
+    struct WireHeader {
+        kind: u8,
+        len: u32,
+    }
+
+    #[allow(dead_code)]
+    #[derive(crate::HasField)]
+    union Word {
+        bytes: [u8; 4],
+        value: u32,
+    }
+
+    assert_lacks_field!(WireHeader, checksum);
+    assert_lacks_field!(WireHeader, len: u16);
+    assert_lacks_field!(Word, value: i32);
+
+    #[allow(dead_code)]
+    struct UserId(u64);
+
+    #[allow(dead_code)]
+    #[derive(crate::HasField)]
+    pub struct UserDto {
+        pub name: &'static str,
+        id: UserId,
+    }
+
+    assert_has_field!(UserDto, { name: &'static str, id: UserId });
+    assert_has_field!(derived UserDto, name: &'static str);
+    assert_lacks_field!(UserDto, email);
+    assert_lacks_field!(UserDto, id: u64);
+    assert_has_field!(WireHeader, len: field_type!(WireHeader, len));
+
+    const POINT_X: &str = field_name!(Point, x);
+    static CONNECTION_PEER: &str = field_name!(strict Connection, peer.1);
+
+    #[test]
+    fn field_names() {
+        assert_eq!(POINT_X, "x");
+        assert_eq!(CONNECTION_PEER, "peer.1");
+        assert_eq!(field_name!(deref Wrapper<Point>, y), "y");
+        assert_eq!(field_name!(Frame, header.0), "header.0");
+        assert_eq!(field_name!(Line, start.x), "start.x");
+        assert_eq!(field_name!((u8, (u16, Point)), 1.1.x), "1.1.x");
+        assert_eq!(field_name!(enum Event<u8>::Created, at.0), "at.0");
+    }
+
+    #[allow(dead_code)]
+    fn profile_id<T>(profile: &Profile<T>) -> &field_type!(Profile<T>, id) {
+        &profile.id
+    }
+
+    #[allow(dead_code)]
+    fn first_id<T>(items: &[T]) -> Option<&field_type!(T, id)>
+    where
+        T: crate::HasField<{ crate::field_id("id") }>,
+    {
+        let _ = items;
+        None
+    }
+
+    assert_has_field!(Pair, 1.id: field_type!(Pair, 1.id));
+    assert_has_field!(for<T> Profile<T>, name: field_type!(Profile<T>, name));
+
+    #[test]
+    fn field_types() {
+        let ids: Vec<field_type!(Pair, 1.id)> = Vec::from([1, 2]);
+        let _: Vec<u64> = ids;
+        let tag: field_type!(Pair, 0) = 7;
+        let _: u8 = tag;
+        let name: &field_type!(Profile<u8>, name) = &"name";
+        let _: &&'static str = name;
+    }
+
+    #[allow(dead_code)]
+    #[derive(crate::HasField)]
+    struct Request<T: ?Sized> {
+        #[has_field(get)]
+        request_id: u64,
+        #[has_field(get)]
+        header: Profile<u8>,
+        #[has_field(get)]
+        body: T,
+    }
+
+    assert_has_field!(derived Request<[u8]>, {
+        request_id: u64,
+        header.id :~ u8,
+        header.name: impl Copy,
+        body: [u8],
+        body: ![u16],
+    });
+    assert_has_field!(derived for<T> Request<T>, { body: T, header.id :> u16 });
+    assert_has_field!(derived Pair, { 0: u8, 1.id: u64 });
+
+    #[allow(dead_code)]
+    #[derive(crate::HasField)]
+    struct Pairs(u16, pub Pair);
+
+    assert_has_field!(derived Pairs, { 1 .0: u8, 1 .1.id: u64 });
+    assert_has_field!(Pairs, 1.1.id: field_type!(Pairs, 1 .1.id));
+
+    fn request_id<T>(value: &mut T) -> &mut u64
+    where
+        T: ?Sized + crate::GetField<{ crate::field_id("request_id") }, Type = u64>,
+    {
+        assert_has_field!(let derived T, request_id: u64);
+        value.get_mut()
+    }
+
+    #[test]
+    fn derived_accessors() {
+        use crate::{GetField, field_id};
+
+        let mut request = Request {
+            request_id: 1,
+            header: Profile {
+                name: "header",
+                id: 2,
+            },
+            body: [3u8, 4],
+        };
+        *request_id(&mut request) += 10;
+        assert_eq!(request.request_id, 11);
+        assert_eq!(
+            *GetField::<{ field_id("name") }>::get(&request.header),
+            "header"
+        );
+
+        let unsized_request: &mut Request<[u8]> = &mut request;
+        GetField::<{ field_id("body") }>::get_mut(unsized_request)[1] = 5;
+        assert_eq!(request.body, [3, 5]);
+
+        let mut pair = Pair(
+            1,
+            Profile {
+                name: "pair",
+                id: 7,
+            },
+        );
+        *GetField::<{ field_id("0") }>::get_mut(&mut pair) = 2;
+        assert_eq!(pair.0, 2);
+        assert_eq!(
+            *GetField::<{ field_id("id") }>::get(GetField::<{ field_id("1") }>::get(&pair)),
+            7
+        );
+    }
+
+    fn wrapped<T>(wrapper: &Wrapper<T>) -> &T {
+        let inner = field_getter!(strict Wrapper<T>, 0);
+        inner(wrapper)
+    }
+
+    #[test]
+    fn field_getters() {
+        let mut lines = Vec::from([
+            Line {
+                start: Point { x: 2, y: 0 },
+                end: Point { x: 0, y: 0 },
+                weights: ((1, 2), 3),
+            },
+            Line {
+                start: Point { x: 1, y: 0 },
+                end: Point { x: 0, y: 0 },
+                weights: ((4, 5), 6),
+            },
+        ]);
+
+        let start_x = field_getter!(Line, start.x);
+        lines.sort_by_key(|line| *start_x(line));
+        assert_eq!(lines[0].weights.1, 6);
+
+        let weight = field_getter!(mut strict Line, weights.0.1);
+        *weight(&mut lines[1]) = 7;
+        assert_eq!(lines[1].weights.0.1, 7);
+
+        let deref_x = field_getter!(deref Wrapper<Point>, x);
+        assert_eq!(*deref_x(&Wrapper(Point { x: 8, y: 9 })), 8);
+        assert_eq!(*wrapped(&Wrapper(10u8)), 10);
+
+        let _: fn(&Packet) -> &[u8] = field_getter!(Packet, data);
+        let _: fn(&mut Connection) -> &mut String = field_getter!(mut Connection, peer.1);
+    }
+
+    assert_has_field!([Point, Wrapper<Point>], x: u64);
+    assert_has_field!(strict [Point,], { x: !u8, y });
+    assert_has_field!(for<T> [Wrapper<T>, Wrapper<Vec<T>>], 0);
+    assert_has_field!([Point, Wrapper<Point>], { x :> u128, x :?> u8, y: !u32 });
+
+    impl Point {
+        #[allow(dead_code)]
+        fn check_list(&self) {
+            assert_has_field!(let [Self, Wrapper<Self>], x: u64)
+        }
+    }
+
+    field_shape!(Planar { x: u64, y: u64 });
+    field_shape!(Segment { start.x :~ u64, end: Point, weights.0 });
+
+    assert_shape!(Point: Planar);
+    assert_shape!(strict Line: Segment);
+    assert_shape!(deref Wrapper<Point>: Planar);
+
+    mod shapes {
+        field_shape!(Spatial { x: u64 });
+
+        // Makes the shape nameable by a path.
+        #[allow(clippy::single_component_path_imports)]
+        pub(crate) use Spatial;
+    }
+
+    assert_shape!(Point: Planar + shapes::Spatial);
+    assert_shape!(Point: self::shapes::Spatial);
+
+    impl Line {
+        #[allow(dead_code)]
+        fn check_shapes(&self) {
+            assert_shape!(let Self: Segment);
+            assert_shape!(let strict Self: Segment)
+        }
+    }
 }